@@ -1,10 +1,72 @@
-use std::{time::{Instant, Duration}, collections::BTreeMap, fmt::Display};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    baseline::{Baseline, Delta},
+    report::{DayReport, Format, Report, Stats, TotalReport},
+    run::ParallelAnalyzer,
+    Answer, Answers, ParseError, SolveError, Verdict,
+};
+
+/// Size of a puzzle's input, passed to [Analyzer::before_parse]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InputInfo {
+    pub bytes: usize,
+    pub lines: usize,
+}
+
+impl InputInfo {
+    pub fn new(input: &str) -> Self {
+        InputInfo {
+            bytes: input.len(),
+            lines: input.lines().count(),
+        }
+    }
+}
+
+/// How running a puzzle's part ended, passed to [Analyzer::after_part]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome<'a> {
+    /// The part was solved with the given answer
+    Solved(&'a Answer),
+    /// The part failed with the given error
+    Failed(&'a SolveError),
+}
+
+impl Outcome<'_> {
+    /// The answer of a solved part
+    pub fn answer(&self) -> Option<&Answer> {
+        match self {
+            Outcome::Solved(answer) => Some(answer),
+            Outcome::Failed(_) => None,
+        }
+    }
+}
 
 /// Scaffold metr an AoC runner
 pub trait Analyzer {
+    /// Whether the runner should print progress and answers while running
+    fn verbose(&self) -> bool {
+        true
+    }
+
     /// Called before running all puzzles
     fn before_all(&mut self) {}
 
+    /// Number of times each day is parsed and each part is run, each time on a fresh instance
+    fn repeat(&self) -> usize {
+        1
+    }
+
+    /// Time after which a part is abandoned and reported as failed, if any
+    ///
+    /// Parts with a timeout run on a separate thread, which is left running when timed out.
+    fn timeout(&self) -> Option<Duration> {
+        None
+    }
+
     /// Called after running all puzzles
     fn after_all(&mut self) {}
 
@@ -14,17 +76,214 @@ pub trait Analyzer {
     /// Called after running a day's puzzle
     fn after_day(&mut self, _day: usize) {}
 
-    /// Called before parsing a puzzle's input
-    fn before_parse(&mut self, _day: usize) {}
+    /// Called before parsing a puzzle's input of the given size
+    fn before_parse(&mut self, _day: usize, _input: &InputInfo) {}
 
-    /// Called after parsing a puzzle's input
-    fn after_parse(&mut self, _day: usize) {}
+    /// Called after parsing a puzzle's input, with the error if parsing failed
+    fn after_parse(&mut self, _day: usize, _result: Result<(), &ParseError>) {}
 
     /// Called before running a puzle's part
     fn before_part(&mut self, _day: usize, _part: usize) {}
 
-    /// Called after running a puzle's part
-    fn after_part(&mut self, _day: usize, _part: usize) {}
+    /// Called after running a puzle's part, with its answer or the error it failed with
+    ///
+    /// Called for each sample of repeated runs.
+    fn after_part(&mut self, _day: usize, _part: usize, _outcome: Outcome) {}
+
+    /// Called after a puzzle's part was verified against its known answer
+    fn part_verified(&mut self, _day: usize, _part: usize, _verdict: Verdict) {}
+}
+
+/// Calls the hooks of several analyzers, e.g. `(MemoryAnalyzer::new(), TimeAnalyzer::new())`
+///
+/// Hooks before a step are called in order, hooks after a step in reverse order, so the last analyzer
+/// is closest to the measured step. Progress is only printed if all analyzers are verbose, and days
+/// are repeated as often as any analyzer asks for. The shortest timeout of all analyzers applies.
+macro_rules! impl_analyzer_for_tuple {
+    ($($name:ident: $idx:tt),+; $($rev:tt),+) => {
+        impl<$($name: Analyzer),+> Analyzer for ($($name,)+) {
+            fn verbose(&self) -> bool {
+                $(self.$idx.verbose())&&+
+            }
+
+            fn before_all(&mut self) {
+                $(self.$idx.before_all();)+
+            }
+
+            fn repeat(&self) -> usize {
+                1 $(.max(self.$idx.repeat()))+
+            }
+
+            fn timeout(&self) -> Option<Duration> {
+                [$(self.$idx.timeout()),+].into_iter().flatten().min()
+            }
+
+            fn after_all(&mut self) {
+                $(self.$rev.after_all();)+
+            }
+
+            fn before_day(&mut self, day: usize) {
+                $(self.$idx.before_day(day);)+
+            }
+
+            fn after_day(&mut self, day: usize) {
+                $(self.$rev.after_day(day);)+
+            }
+
+            fn before_parse(&mut self, day: usize, input: &InputInfo) {
+                $(self.$idx.before_parse(day, input);)+
+            }
+
+            fn after_parse(&mut self, day: usize, result: Result<(), &ParseError>) {
+                $(self.$rev.after_parse(day, result);)+
+            }
+
+            fn before_part(&mut self, day: usize, part: usize) {
+                $(self.$idx.before_part(day, part);)+
+            }
+
+            fn after_part(&mut self, day: usize, part: usize, outcome: Outcome) {
+                $(self.$rev.after_part(day, part, outcome);)+
+            }
+
+            fn part_verified(&mut self, day: usize, part: usize, verdict: Verdict) {
+                $(self.$idx.part_verified(day, part, verdict);)+
+            }
+        }
+
+        impl<$($name: ParallelAnalyzer),+> ParallelAnalyzer for ($($name,)+) {
+            fn fork(&self) -> Self {
+                ($(self.$idx.fork(),)+)
+            }
+
+            fn merge(&mut self, other: Self) {
+                $(self.$idx.merge(other.$idx);)+
+            }
+        }
+    };
+}
+
+impl_analyzer_for_tuple!(A: 0, B: 1; 1, 0);
+impl_analyzer_for_tuple!(A: 0, B: 1, C: 2; 2, 1, 0);
+impl_analyzer_for_tuple!(A: 0, B: 1, C: 2, D: 3; 3, 2, 1, 0);
+
+/// Calls the hooks of all analyzers in the same order as a tuple of analyzers does
+impl<A: Analyzer> Analyzer for Vec<A> {
+    fn verbose(&self) -> bool {
+        self.iter().all(A::verbose)
+    }
+
+    fn before_all(&mut self) {
+        self.iter_mut().for_each(A::before_all);
+    }
+
+    fn repeat(&self) -> usize {
+        self.iter().map(A::repeat).fold(1, usize::max)
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.iter().filter_map(A::timeout).min()
+    }
+
+    fn after_all(&mut self) {
+        self.iter_mut().rev().for_each(A::after_all);
+    }
+
+    fn before_day(&mut self, day: usize) {
+        self.iter_mut().for_each(|it| it.before_day(day));
+    }
+
+    fn after_day(&mut self, day: usize) {
+        self.iter_mut().rev().for_each(|it| it.after_day(day));
+    }
+
+    fn before_parse(&mut self, day: usize, input: &InputInfo) {
+        self.iter_mut().for_each(|it| it.before_parse(day, input));
+    }
+
+    fn after_parse(&mut self, day: usize, result: Result<(), &ParseError>) {
+        self.iter_mut()
+            .rev()
+            .for_each(|it| it.after_parse(day, result));
+    }
+
+    fn before_part(&mut self, day: usize, part: usize) {
+        self.iter_mut().for_each(|it| it.before_part(day, part));
+    }
+
+    fn after_part(&mut self, day: usize, part: usize, outcome: Outcome) {
+        self.iter_mut()
+            .rev()
+            .for_each(|it| it.after_part(day, part, outcome));
+    }
+
+    fn part_verified(&mut self, day: usize, part: usize, verdict: Verdict) {
+        self.iter_mut()
+            .for_each(|it| it.part_verified(day, part, verdict));
+    }
+}
+
+impl<A: ParallelAnalyzer> ParallelAnalyzer for Vec<A> {
+    fn fork(&self) -> Self {
+        self.iter().map(A::fork).collect()
+    }
+
+    fn merge(&mut self, other: Self) {
+        self.iter_mut()
+            .zip(other)
+            .for_each(|(it, other)| it.merge(other));
+    }
+}
+
+/// Allows mixing analyzers of different types, e.g. in a `Vec<Box<dyn Analyzer>>`
+impl<A: Analyzer + ?Sized> Analyzer for Box<A> {
+    fn verbose(&self) -> bool {
+        (**self).verbose()
+    }
+
+    fn before_all(&mut self) {
+        (**self).before_all()
+    }
+
+    fn repeat(&self) -> usize {
+        (**self).repeat()
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        (**self).timeout()
+    }
+
+    fn after_all(&mut self) {
+        (**self).after_all()
+    }
+
+    fn before_day(&mut self, day: usize) {
+        (**self).before_day(day)
+    }
+
+    fn after_day(&mut self, day: usize) {
+        (**self).after_day(day)
+    }
+
+    fn before_parse(&mut self, day: usize, input: &InputInfo) {
+        (**self).before_parse(day, input)
+    }
+
+    fn after_parse(&mut self, day: usize, result: Result<(), &ParseError>) {
+        (**self).after_parse(day, result)
+    }
+
+    fn before_part(&mut self, day: usize, part: usize) {
+        (**self).before_part(day, part)
+    }
+
+    fn after_part(&mut self, day: usize, part: usize, outcome: Outcome) {
+        (**self).after_part(day, part, outcome)
+    }
+
+    fn part_verified(&mut self, day: usize, part: usize, verdict: Verdict) {
+        (**self).part_verified(day, part, verdict)
+    }
 }
 
 #[derive(Default)]
@@ -34,9 +293,20 @@ pub struct TimeAnalyzer {
     start_day: Option<Instant>,
     time_days: BTreeMap<usize, Duration>,
     start_parse: Option<Instant>,
-    time_parse: BTreeMap<usize, Duration>,
+    /// Samples of parse timings per day
+    time_parse: BTreeMap<usize, Vec<Duration>>,
     start_part: Option<Instant>,
-    time_part: BTreeMap<(usize,usize), Duration>,
+    /// Samples of part timings per (day, part)
+    time_part: BTreeMap<(usize, usize), Vec<Duration>>,
+    /// (day, part) tuples that failed, where part 0 denotes parsing
+    failed: BTreeSet<(usize, usize)>,
+    verdicts: BTreeMap<(usize, usize), Verdict>,
+    answers: BTreeMap<usize, Answers>,
+    format: Format,
+    /// Timings of a previous run along with the relative slowdown that counts as regression
+    baseline: Option<(Baseline, f64)>,
+    repeat: usize,
+    timeout: Option<Duration>,
 }
 
 /// A simple analyzer that measures and prints run times
@@ -45,66 +315,106 @@ impl TimeAnalyzer {
         TimeAnalyzer::default()
     }
 
+    /// Sets the format the report is printed in
+    pub fn with_format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Repeats parsing and running each part `repeat` times to collect timing statistics
+    pub fn with_repeat(mut self, repeat: usize) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    /// Abandons parts running longer than `timeout`, reporting them as failed
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
     fn days(&self) -> Vec<usize> {
         self.time_days.keys().cloned().collect()
     }
 
-    fn total_parse(&self) -> Duration {
-        self.days().iter().map(|day| self.time_parse.get(day).unwrap()).cloned().reduce(|a,b| a.saturating_add(b)).unwrap_or_default()
+    /// Statistics over the samples of `part` of `day`, where part 0 denotes parsing
+    fn stats(&self, day: usize, part: usize) -> Option<Stats> {
+        let samples = match part {
+            0 => self.time_parse.get(&day),
+            part => self.time_part.get(&(day, part)),
+        }?;
+        Stats::new(samples)
     }
 
-    fn total_part1(&self) -> Duration {
-        self.days().iter().filter_map(|day| self.time_part.get(&(*day, 1)))
-            .cloned().reduce(|a,b| a.saturating_add(b)).unwrap_or_default()
+    /// The median timing of `part` of `day`, where part 0 denotes parsing
+    fn time(&self, day: usize, part: usize) -> Option<Duration> {
+        self.stats(day, part).map(|stats| stats.median)
     }
 
-    fn total_part2(&self) -> Duration {
-        self.days().iter().filter_map(|day| self.time_part.get(&(*day, 2)))
-            .cloned().reduce(|a,b| a.saturating_add(b)).unwrap_or_default()
+    fn total_part(&self, part: usize) -> Duration {
+        self.days()
+            .iter()
+            .filter_map(|day| self.time(*day, part))
+            .reduce(|a, b| a.saturating_add(b))
+            .unwrap_or_default()
     }
 
     fn total(&self) -> Duration {
-        self.time_all.unwrap_or(self.time_days.values().sum())
-    }
-
-    fn report(&mut self) {
-        fn print_line(day: impl Display, parse: Duration, part1: Duration, part2: Duration, total: Duration) {
-            print!("| {:>6} |", day);
-            print_col(parse);
-            print_col(part1);
-            print_col(part2);
-            print_col(total);
-            print!("\n");
+        // the overall time spans all samples, so fall back to the per sample day times
+        match self.time_all {
+            Some(time_all) if self.repeat <= 1 => time_all,
+            _ => self.time_days.values().sum(),
         }
+    }
 
-        fn print_col(duration: Duration) {
-            if duration.as_secs() > 0 {
-                print!(" {:>7}s |", duration.as_secs());
-            } else if duration.as_millis() > 0 {
-                print!(" {:>6}ms |", duration.as_millis());
-            } else if duration.as_micros() > 0 {
-                print!(" {:>6}μs |", duration.as_micros());
-            } else {
-                print!(" {:>6}ns |", duration.as_nanos());
-            }
-        }
+    /// Compares the measured timings against `baseline`, flagging parts that got slower by more
+    /// than `threshold` (e.g. `0.1` for 10%)
+    pub fn with_baseline(mut self, baseline: Baseline, threshold: f64) -> Self {
+        self.baseline = Some((baseline, threshold));
+        self
+    }
 
-        println!();
-        println!("+--------|----------|----------|----------|----------+");
-        println!("| Day    | Parse    | Part 1   | Part 2   | Total    |");
-        println!("+--------|----------|----------|----------|----------+");
-        print_line("Total", self.total_parse(), self.total_part1(), self.total_part2(), self.total());
-        println!("+----------------------------------------------------+");
-        for ref day in self.days() {
-            print_line(
+    /// Collects the measured timings and answers into a [Report]
+    pub fn report(&self) -> Report {
+        let total = TotalReport {
+            parse: self.total_part(0),
+            parts: [self.total_part(1), self.total_part(2)],
+            total: self.total(),
+        };
+        let days = self
+            .days()
+            .into_iter()
+            .map(|day| DayReport {
                 day,
-                self.time_parse.get(day).cloned().unwrap_or_default(),
-                self.time_part.get(&(*day, 1)).cloned().unwrap_or_default(),
-                self.time_part.get(&(*day, 2)).cloned().unwrap_or_default(),
-                self.time_days.get(day).cloned().unwrap_or_default(),
-            );
+                parse: self.time(day, 0),
+                parts: [1, 2].map(|part| self.time(day, part)),
+                total: self.time_days.get(&day).cloned(),
+                failed: [0, 1, 2].map(|part| self.failed.contains(&(day, part))),
+                answers: self.answers.get(&day).cloned().unwrap_or_default(),
+                verdicts: [1, 2].map(|part| self.verdicts.get(&(day, part)).cloned()),
+                deltas: [0, 1, 2].map(|part| self.delta(day, part)),
+                stats: [0, 1, 2].map(|part| self.stats(day, part)),
+            })
+            .collect();
+        Report { total, days }
+    }
+
+    fn delta(&self, day: usize, part: usize) -> Option<Delta> {
+        let (baseline, threshold) = self.baseline.as_ref()?;
+        if self.failed.contains(&(day, part)) {
+            return None;
         }
-        println!("+--------|----------|----------|----------|----------+");
+        Some(Delta::new(
+            self.time(day, part)?,
+            baseline.get(day, part)?,
+            *threshold,
+        ))
+    }
+
+    fn print_report(&self) {
+        self.report()
+            .write(self.format, &mut std::io::stdout().lock())
+            .expect("Could not write report");
     }
 }
 
@@ -115,7 +425,7 @@ impl Analyzer for TimeAnalyzer {
 
     fn after_all(&mut self) {
         self.time_all = Some(self.start_all.unwrap().elapsed());
-        self.report();
+        self.print_report();
     }
 
     fn before_day(&mut self, _day: usize) {
@@ -123,26 +433,165 @@ impl Analyzer for TimeAnalyzer {
     }
 
     fn after_day(&mut self, day: usize) {
-        self.time_days.insert(day, self.start_day.unwrap().elapsed());
+        // average over all samples of the day
+        let samples = self.time_parse.get(&day).map_or(1, Vec::len).max(1);
+        self.time_days
+            .insert(day, self.start_day.unwrap().elapsed() / samples as u32);
 
         if self.start_all.is_none() {
-            self.report();
+            self.print_report();
         }
     }
 
-    fn before_parse(&mut self, _day: usize) {
+    fn before_parse(&mut self, _day: usize, _input: &InputInfo) {
         self.start_parse = Some(Instant::now());
     }
 
-    fn after_parse(&mut self, day: usize) {
-        self.time_parse.insert(day, self.start_parse.unwrap().elapsed());
+    fn after_parse(&mut self, day: usize, result: Result<(), &ParseError>) {
+        self.time_parse
+            .entry(day)
+            .or_default()
+            .push(self.start_parse.unwrap().elapsed());
+        if result.is_err() {
+            self.failed.insert((day, 0));
+        }
     }
 
     fn before_part(&mut self, _day: usize, _part: usize) {
         self.start_part = Some(Instant::now());
     }
 
-    fn after_part(&mut self, day: usize, part: usize) {
-        self.time_part.insert((day, part), self.start_part.unwrap().elapsed());
+    fn after_part(&mut self, day: usize, part: usize, outcome: Outcome) {
+        self.time_part
+            .entry((day, part))
+            .or_default()
+            .push(self.start_part.unwrap().elapsed());
+        match outcome {
+            // answers of repeated samples are the same
+            Outcome::Solved(answer) => {
+                self.answers
+                    .entry(day)
+                    .or_default()
+                    .set(part, answer.clone());
+            }
+            Outcome::Failed(_) => {
+                self.failed.insert((day, part));
+            }
+        }
+    }
+
+    fn part_verified(&mut self, day: usize, part: usize, verdict: Verdict) {
+        self.verdicts.insert((day, part), verdict);
+    }
+
+    fn repeat(&self) -> usize {
+        self.repeat.max(1)
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    fn verbose(&self) -> bool {
+        // keep machine-readable output free of progress prints
+        self.format == Format::Table
+    }
+}
+
+impl ParallelAnalyzer for TimeAnalyzer {
+    fn fork(&self) -> Self {
+        TimeAnalyzer {
+            // a started run keeps the forks from reporting single days
+            start_all: self.start_all,
+            format: self.format,
+            repeat: self.repeat,
+            timeout: self.timeout,
+            ..Default::default()
+        }
+    }
+
+    fn merge(&mut self, other: Self) {
+        self.time_days.extend(other.time_days);
+        self.time_parse.extend(other.time_parse);
+        self.time_part.extend(other.time_part);
+        self.failed.extend(other.failed);
+        self.verdicts.extend(other.verdicts);
+        self.answers.extend(other.answers);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::*;
+
+    /// Records the hooks called on it into a log shared with other analyzers
+    struct Log(&'static str, Rc<RefCell<Vec<String>>>, usize);
+
+    impl Analyzer for Log {
+        fn repeat(&self) -> usize {
+            self.2
+        }
+
+        fn before_part(&mut self, day: usize, part: usize) {
+            self.1
+                .borrow_mut()
+                .push(format!("{} before {}.{}", self.0, day, part));
+        }
+
+        fn after_part(&mut self, day: usize, part: usize, outcome: Outcome) {
+            let answer = outcome.answer().map(Answer::to_string).unwrap_or_default();
+            self.1
+                .borrow_mut()
+                .push(format!("{} after {}.{} {}", self.0, day, part, answer));
+        }
+    }
+
+    #[test]
+    fn input_info() {
+        assert_eq!(InputInfo::new("1\n22\n"), InputInfo { bytes: 5, lines: 2 });
+        assert_eq!(InputInfo::new(""), InputInfo::default());
+    }
+
+    #[test]
+    fn combined() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut analyzer = (Log("a", log.clone(), 1), Log("b", log.clone(), 3));
+        analyzer.before_part(1, 2);
+        analyzer.after_part(1, 2, Outcome::Solved(&Answer::Int(42)));
+        assert_eq!(
+            *log.borrow(),
+            [
+                "a before 1.2",
+                "b before 1.2",
+                "b after 1.2 42",
+                "a after 1.2 42"
+            ]
+        );
+        assert_eq!(analyzer.repeat(), 3);
+        assert!((analyzer, TimeAnalyzer::new()).verbose());
+        assert!(!(
+            TimeAnalyzer::new(),
+            TimeAnalyzer::new().with_format(Format::Json)
+        )
+            .verbose());
+
+        log.borrow_mut().clear();
+        let mut analyzer: Vec<Box<dyn Analyzer>> = vec![
+            Box::new(Log("a", log.clone(), 1)),
+            Box::new(Log("b", log.clone(), 1)),
+        ];
+        analyzer.before_part(3, 1);
+        analyzer.after_part(3, 1, Outcome::Failed(&SolveError::NoSolution));
+        assert_eq!(
+            *log.borrow(),
+            [
+                "a before 3.1",
+                "b before 3.1",
+                "b after 3.1 ",
+                "a after 3.1 "
+            ]
+        );
     }
 }
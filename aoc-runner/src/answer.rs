@@ -0,0 +1,128 @@
+//! Typed answers of puzzles, which every part's result converts to
+
+use std::fmt::Display;
+
+/// The answer of a puzzle's part
+///
+/// Answers are compared, serialized and submitted uniformly, whatever type a part returns. An
+/// answer's [Display] form is the one to submit, lists are joined by commas. Answers read as text
+/// (e.g. from an answers file) are converted with [Answer::from], so they compare equal to the
+/// typed answers of the parts.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i128),
+    Str(String),
+    List(Vec<Answer>),
+}
+
+impl Answer {
+    /// Converts a single list element, which is an integer if it reads the same when printed again
+    fn atom(s: &str) -> Self {
+        match s.parse::<i128>() {
+            Ok(n) if n.to_string() == s => Answer::Int(n),
+            _ => Answer::Str(s.to_string()),
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::Str(s) => f.write_str(s),
+            Answer::List(items) => {
+                for (idx, item) in items.iter().enumerate() {
+                    if idx > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Int(n.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Int(n as i128)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(n: isize) -> Self {
+        Answer::Int(n as i128)
+    }
+}
+
+impl From<u128> for Answer {
+    /// Integers beyond [i128::MAX] are kept as text
+    fn from(n: u128) -> Self {
+        i128::try_from(n).map_or_else(|_| Answer::Str(n.to_string()), Answer::Int)
+    }
+}
+
+impl From<&str> for Answer {
+    /// Reads an answer from its text, which is a list if it contains commas
+    fn from(s: &str) -> Self {
+        if s.contains(',') {
+            Answer::List(s.split(',').map(Answer::atom).collect())
+        } else {
+            Answer::atom(s)
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        s.as_str().into()
+    }
+}
+
+impl<T: Into<Answer>> From<Vec<T>> for Answer {
+    fn from(items: Vec<T>) -> Self {
+        Answer::List(items.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<A: Into<Answer>, B: Into<Answer>> From<(A, B)> for Answer {
+    fn from((a, b): (A, B)) -> Self {
+        Answer::List(vec![a.into(), b.into()])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn answer() {
+        assert_eq!(Answer::from(42_u32), Answer::Int(42));
+        assert_eq!(Answer::from("-7"), Answer::Int(-7));
+        assert_eq!(Answer::from("007"), Answer::Str("007".to_string()));
+        assert_eq!(Answer::from(u128::MAX).to_string(), u128::MAX.to_string());
+
+        let list = Answer::from(vec![4_u128, 6, 3]);
+        assert_eq!(list.to_string(), "4,6,3");
+        assert_eq!(Answer::from("4,6,3"), list);
+        assert_eq!(Answer::from((6, 1)), Answer::from("6,1".to_string()));
+        assert_eq!(
+            Answer::from(vec!["co", "de"]),
+            Answer::List(vec![Answer::Str("co".into()), Answer::Str("de".into())])
+        );
+    }
+}
@@ -0,0 +1,143 @@
+//! Timing baselines of previous runs, used to detect regressions
+
+use std::{collections::BTreeMap, fmt::Display, io, path::Path, time::Duration};
+
+use crate::Report;
+
+/// Per-part timings of a previous run
+///
+/// Part 0 denotes parsing. Stored as text with one `<day> <part> <nanoseconds>` line per part.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Baseline(BTreeMap<(usize, usize), Duration>);
+
+impl Baseline {
+    /// Parses a baseline file, ignoring empty lines and comments starting with `#`
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let mut baseline = Self::default();
+        for (idx, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields = line
+                .split_whitespace()
+                .map(str::parse::<u64>)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|err| format!("line {}: {}", idx + 1, err))?;
+            let [day, part, nanos] = fields[..] else {
+                return Err(format!(
+                    "line {}: expected <day> <part> <nanoseconds>",
+                    idx + 1
+                ));
+            };
+            baseline.set(day as usize, part as usize, Duration::from_nanos(nanos));
+        }
+        Ok(baseline)
+    }
+
+    /// Loads a baseline from `path`, returning an empty baseline if the file does not exist
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(s) => Self::parse(&s).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Saves this baseline to `path`
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        std::fs::write(path, self.to_string())
+    }
+
+    /// Returns the baseline timing of `part` of `day`, where part 0 denotes parsing
+    pub fn get(&self, day: usize, part: usize) -> Option<Duration> {
+        self.0.get(&(day, part)).cloned()
+    }
+
+    /// Sets the baseline timing of `part` of `day`, where part 0 denotes parsing
+    pub fn set(&mut self, day: usize, part: usize, duration: Duration) {
+        self.0.insert((day, part), duration);
+    }
+
+    /// Updates this baseline with all timings of `report` that did not fail
+    pub fn update(&mut self, report: &Report) {
+        for day in report.days.iter() {
+            let durations = [day.parse, day.parts[0], day.parts[1]];
+            for (part, (duration, failed)) in durations.into_iter().zip(day.failed).enumerate() {
+                if let (Some(duration), false) = (duration, failed) {
+                    self.set(day.day, part, duration);
+                }
+            }
+        }
+    }
+}
+
+impl Display for Baseline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "# day part nanoseconds")?;
+        for ((day, part), duration) in self.0.iter() {
+            writeln!(f, "{} {} {}", day, part, duration.as_nanos())?;
+        }
+        Ok(())
+    }
+}
+
+/// The change of a timing relative to its baseline
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Delta {
+    /// Relative change, e.g. `0.1` if the timing got 10% slower
+    pub ratio: f64,
+    /// Whether the timing got slower than the configured threshold
+    pub regression: bool,
+}
+
+impl Delta {
+    /// Compares `duration` against `baseline`, flagging it as regression if it got slower by more
+    /// than `threshold` (e.g. `0.1` for 10%)
+    pub fn new(duration: Duration, baseline: Duration, threshold: f64) -> Self {
+        let ratio = duration.as_secs_f64() / baseline.as_secs_f64().max(f64::MIN_POSITIVE) - 1.0;
+        Self {
+            ratio,
+            regression: ratio > threshold,
+        }
+    }
+}
+
+impl Display for Delta {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let flag = if self.regression { "!" } else { "" };
+        let delta = if self.ratio.abs() >= 10.0 {
+            format!("{:+.0}x{}", self.ratio + 1.0, flag)
+        } else {
+            format!("{:+.1}%{}", self.ratio * 100.0, flag)
+        };
+        f.pad(&delta)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_and_display() {
+        let mut baseline = Baseline::default();
+        baseline.set(1, 0, Duration::from_nanos(10));
+        baseline.set(1, 2, Duration::from_nanos(30));
+        let s = baseline.to_string();
+        assert_eq!(s, "# day part nanoseconds\n1 0 10\n1 2 30\n");
+        assert_eq!(Baseline::parse(&s), Ok(baseline));
+        assert!(Baseline::parse("1 2").is_err());
+    }
+
+    #[test]
+    fn delta() {
+        let baseline = Duration::from_micros(100);
+        let slower = Delta::new(Duration::from_micros(125), baseline, 0.1);
+        assert!(slower.regression);
+        assert_eq!(slower.to_string(), "+25.0%!");
+        let faster = Delta::new(Duration::from_micros(95), baseline, 0.1);
+        assert!(!faster.regression);
+        assert_eq!(faster.to_string(), "-5.0%");
+    }
+}
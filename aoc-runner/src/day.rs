@@ -1,26 +1,46 @@
 //! Defines a common interface for [Advent of Code](http://adventofcode.com) puzzles
 
+use std::io::{self, Write};
+
+use crate::{Answer, ParamError, Params, ParseError, SolveError};
+
 /// A day's challenge
 pub trait Day: Default {
-    type Result1: std::fmt::Display + Sized;
-    type Result2: std::fmt::Display + Sized;
+    type Result1: Into<Answer> + Sized;
+    type Result2: Into<Answer> + Sized;
 
     /// Part 1 of this day's challenge
-    fn part1(&mut self) -> Self::Result1;
+    fn part1(&mut self) -> Result<Self::Result1, SolveError>;
 
     /// Part 2 of this day's challenge
-    fn part2(&mut self) -> Self::Result2;
+    fn part2(&mut self) -> Result<Self::Result2, SolveError>;
 
     /// Print result of part 1
-    fn print_part1(&self, result: Self::Result1) {
-        println!(" - Part 1: {}", result)
+    fn print_part1(&self, out: &mut dyn Write, answer: &Answer) -> io::Result<()> {
+        writeln!(out, " - Part 1: {}", answer)
     }
 
     /// Print result of part 2
-    fn print_part2(&self, result: Self::Result2) {
-        println!(" - Part 2: {}", result)
+    fn print_part2(&self, out: &mut dyn Write, answer: &Answer) -> io::Result<()> {
+        writeln!(out, " - Part 2: {}", answer)
+    }
+
+    /// Optional: override default parameters, e.g. the grid size of an example
+    ///
+    /// Called before [Day::parse] with the parameters given on the command line or in the input's
+    /// header, see [Params]. Puzzles without parameters reject all of them.
+    fn configure(&mut self, params: &Params) -> Result<(), ParamError> {
+        params.expect(&[])
     }
 
     /// Optional: parse input to use later in part1/part2
-    fn parse(&mut self, _input: &str) {}
+    fn parse(&mut self, _input: &str) -> Result<(), ParseError> {
+        Ok(())
+    }
+}
+
+/// Visits the days of a runner generically, e.g. to benchmark each of them
+pub trait DayVisitor {
+    /// Called for each `day` with its puzzle
+    fn visit<D: Day + Send + 'static>(&mut self, day: usize, puzzle: &mut D);
 }
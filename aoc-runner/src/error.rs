@@ -0,0 +1,204 @@
+//! Errors raised while running a puzzle
+
+use std::{fmt::Display, time::Duration};
+
+/// An error raised when a puzzle's input cannot be parsed
+///
+/// Positions are 1-based. The day is unknown to the puzzle itself and gets attached by the runner
+/// via [ParseError::with_day].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    day: Option<usize>,
+    line: usize,
+    column: usize,
+    expected: String,
+    found: Option<String>,
+    /// The message of the panic parsing ran into, instead of a position
+    panic: Option<String>,
+}
+
+impl ParseError {
+    /// Creates a new error at the given position
+    pub fn new(
+        line: usize,
+        column: usize,
+        expected: impl Into<String>,
+        found: impl Into<String>,
+    ) -> Self {
+        Self {
+            day: None,
+            line,
+            column,
+            expected: expected.into(),
+            found: Some(found.into()),
+            panic: None,
+        }
+    }
+
+    /// Creates a new error for `found`, which must be a slice of `input`
+    ///
+    /// The error's position is derived from where `found` is located inside of `input`.
+    pub fn at(input: &str, found: &str, expected: impl Into<String>) -> Self {
+        let (line, column) = position(input, found);
+        // only keep the first line of the offending slice, so errors on whole blocks stay readable
+        let found = found.lines().next().unwrap_or_default();
+        Self::new(line, column, expected, found)
+    }
+
+    /// Creates a new error for an input that ended prematurely
+    pub fn end_of_input(input: &str, expected: impl Into<String>) -> Self {
+        let (line, column) = position(input, &input[input.len()..]);
+        Self {
+            day: None,
+            line,
+            column,
+            expected: expected.into(),
+            found: None,
+            panic: None,
+        }
+    }
+
+    /// Creates a new error for a parser that panicked with `message`
+    pub fn panicked(message: impl Into<String>) -> Self {
+        Self {
+            panic: Some(message.into()),
+            ..Self::end_of_input("", "")
+        }
+    }
+
+    /// Attaches the day of the puzzle whose input could not be parsed
+    pub fn with_day(mut self, day: usize) -> Self {
+        self.day = Some(day);
+        self
+    }
+
+    pub fn day(&self) -> Option<usize> {
+        self.day
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn expected(&self) -> &str {
+        &self.expected
+    }
+
+    /// The offending part of the input, or `None` if the input ended prematurely
+    pub fn found(&self) -> Option<&str> {
+        self.found.as_deref()
+    }
+
+    /// The message of the panic parsing ran into, if it panicked
+    pub fn panic(&self) -> Option<&str> {
+        self.panic.as_deref()
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "Day {}: ", day)?;
+        }
+        if let Some(panic) = &self.panic {
+            return write!(f, "Panicked: {}", panic);
+        }
+        write!(
+            f,
+            "line {}, column {}: expected {}, found ",
+            self.line, self.column, self.expected
+        )?;
+        match &self.found {
+            Some(found) => write!(f, "{:?}", found),
+            None => write!(f, "end of input"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Returns the 1-based (line, column) of `slice` inside of `input`
+///
+/// Falls back to (0, 0) if `slice` does not point into `input`.
+fn position(input: &str, slice: &str) -> (usize, usize) {
+    let offset = (slice.as_ptr() as usize).checked_sub(input.as_ptr() as usize);
+    match offset.filter(|offset| *offset <= input.len()) {
+        Some(offset) => {
+            let before = &input[..offset];
+            let line = before.matches('\n').count() + 1;
+            let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+            let column = before[line_start..].chars().count() + 1;
+            (line, column)
+        }
+        None => (0, 0),
+    }
+}
+
+/// An error raised when a puzzle's part cannot be solved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// The input does not admit a solution
+    NoSolution,
+    /// The solver ran into a state its input should not allow for
+    Invalid(String),
+    /// The solver panicked with the given message
+    Panicked(String),
+    /// The solver did not finish within the given time
+    TimedOut(Duration),
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::NoSolution => write!(f, "No solution found"),
+            SolveError::Invalid(msg) => write!(f, "{}", msg),
+            SolveError::Panicked(msg) => write!(f, "Panicked: {}", msg),
+            SolveError::TimedOut(timeout) => write!(f, "Timed out after {:?}", timeout),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+/// An error raised when a puzzle's parameters are malformed or do not apply to the puzzle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParamError {
+    /// The parameter is not given as `name=value`
+    Malformed(String),
+    /// The puzzle has no parameter of this name
+    Unknown { name: String, valid: Vec<String> },
+    /// The value cannot be parsed into the parameter's type
+    InvalidValue { name: String, value: String },
+}
+
+impl Display for ParamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParamError::Malformed(param) => {
+                write!(f, "Malformed parameter: {}. Expected name=value", param)
+            }
+            ParamError::Unknown { name, valid } if valid.is_empty() => {
+                write!(
+                    f,
+                    "Unknown parameter: {}. The puzzle has no parameters",
+                    name
+                )
+            }
+            ParamError::Unknown { name, valid } => write!(
+                f,
+                "Unknown parameter: {}. Valid parameters are: {}",
+                name,
+                valid.join(",")
+            ),
+            ParamError::InvalidValue { name, value } => {
+                write!(f, "Invalid value of parameter {}: {}", name, value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParamError {}
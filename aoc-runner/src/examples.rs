@@ -0,0 +1,341 @@
+//! Example inputs of puzzles along with their expected answers
+//!
+//! Examples are stored as data files in `./examples/<year>/NN/*.txt`, one file per example. A file
+//! starts with the expected answers of the parts it covers, followed by a `---` line and the
+//! example input. An empty answer is unknown and not checked. The input may start with a parameter
+//! header, see [Params]:
+//!
+//! ```text
+//! part1: 11
+//! part2: 31
+//! ---
+//! 3   4
+//! 4   3
+//! ```
+
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::{
+    inputs,
+    run::{catch_panic, solve, solve_with_timeout},
+    Answer, Answers, Day, DayVisitor, Params, ParseError, SolveError, Verdict,
+};
+
+/// An example input along with the expected answers of one or both parts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub name: String,
+    pub answers: Answers,
+    pub input: String,
+}
+
+impl Example {
+    /// Parses the content of an example file
+    pub fn parse(name: impl Into<String>, s: &str) -> Result<Self, String> {
+        let name = name.into();
+        let (header, input) = s
+            .split_once("---\n")
+            .ok_or_else(|| format!("{}: missing `---` line before the input", name))?;
+        let mut answers = Answers::default();
+        for line in header.lines().filter(|it| !it.trim().is_empty()) {
+            let part = match line.split_once(':') {
+                Some(("part1", answer)) => (1, answer),
+                Some(("part2", answer)) => (2, answer),
+                _ => {
+                    return Err(format!(
+                        "{}: expected `partN: <answer>`, found {:?}",
+                        name, line
+                    ))
+                }
+            };
+            if !part.1.trim().is_empty() {
+                answers.set(part.0, part.1.trim());
+            }
+        }
+        Ok(Self {
+            name,
+            answers,
+            input: input.to_string(),
+        })
+    }
+
+    /// Loads all examples in `dir`, ordered by file name
+    ///
+    /// A missing directory holds no examples.
+    pub fn load(dir: &Path) -> io::Result<Vec<Self>> {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+        let mut paths = entries
+            .map(|entry| Ok(entry?.path()))
+            .collect::<io::Result<Vec<PathBuf>>>()?;
+        paths.retain(|path| path.extension().is_some_and(|it| it == "txt"));
+        paths.sort();
+        paths
+            .into_iter()
+            .map(|path| {
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                Self::parse(name, &fs::read_to_string(&path)?)
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+            })
+            .collect()
+    }
+
+    /// Runs this example on a fresh instance of `D`, returning the outcome of each part with an
+    /// expected answer
+    ///
+    /// The instance is configured by the parameter header of the example's input, if any. Panics
+    /// are caught like in [run_puzzle][crate::run::run_puzzle], and a part running longer than
+    /// `timeout` fails along with the parts after it.
+    pub fn check<D: Day + Send + 'static>(
+        &self,
+        day: usize,
+        timeout: Option<Duration>,
+    ) -> Vec<Checked> {
+        let mut puzzle = D::default();
+        let mut parsed = Params::split_header(&self.input)
+            .and_then(|(params, input)| puzzle.configure(&params).map(|_| input))
+            .map_err(|err| format!("invalid parameters: {}", err))
+            .and_then(|input| {
+                catch_panic(|| puzzle.parse(input))
+                    .unwrap_or_else(|message| Err(ParseError::panicked(message)))
+                    .map_err(|err| format!("parse error: {}", err))
+            });
+        [1, 2]
+            .into_iter()
+            .filter_map(|part| {
+                let expected = self.answers.get(part)?.clone();
+                let found = parsed.clone().and_then(|_| {
+                    let answer = match timeout {
+                        Some(timeout) => solve_with_timeout(&mut puzzle, part, timeout),
+                        None => solve(&mut puzzle, part),
+                    };
+                    if let Err(SolveError::TimedOut(_)) = answer {
+                        // the parsed puzzle is left behind on the timed out thread
+                        parsed = Err(format!("skipped after part {} timed out", part));
+                    }
+                    answer.map_err(|err| err.to_string())
+                });
+                Some(Checked {
+                    day,
+                    example: self.name.clone(),
+                    part,
+                    expected,
+                    found,
+                })
+            })
+            .collect()
+    }
+}
+
+/// The outcome of running a part on an example
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checked {
+    pub day: usize,
+    pub example: String,
+    pub part: usize,
+    pub expected: Answer,
+    /// The answer, or the error the part or parsing failed with
+    pub found: Result<Answer, String>,
+}
+
+impl Checked {
+    pub fn verdict(&self) -> Verdict {
+        match &self.found {
+            Ok(found) if *found == self.expected => Verdict::Pass,
+            _ => Verdict::Fail,
+        }
+    }
+}
+
+impl Display for Checked {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Day {:>2} {} part {}: {}",
+            self.day,
+            self.example,
+            self.part,
+            self.verdict()
+        )?;
+        match &self.found {
+            Ok(found) if *found == self.expected => Ok(()),
+            Ok(found) => write!(f, " (expected {}, found {})", self.expected, found),
+            Err(err) => write!(f, " (expected {}, {})", self.expected, err),
+        }
+    }
+}
+
+/// Checks the examples of all visited days
+///
+/// Examples are looked up with [inputs::examples_dir], so they are read relative to the current
+/// directory.
+pub struct ExampleRunner {
+    year: Option<usize>,
+    filter: Box<dyn Fn(usize) -> bool>,
+    timeout: Option<Duration>,
+    checked: Vec<Checked>,
+    errors: Vec<String>,
+}
+
+impl ExampleRunner {
+    pub fn new(year: Option<usize>) -> Self {
+        Self {
+            year,
+            filter: Box::new(|_| true),
+            timeout: None,
+            checked: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Only checks the days `filter` returns `true` for
+    pub fn with_filter(mut self, filter: impl Fn(usize) -> bool + 'static) -> Self {
+        self.filter = Box::new(filter);
+        self
+    }
+
+    /// Fails parts running longer than `timeout`, see [Example::check]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// The outcomes of all parts checked so far
+    pub fn checked(&self) -> &[Checked] {
+        &self.checked
+    }
+
+    /// Errors raised while loading example files
+    pub fn errors(&self) -> &[String] {
+        &self.errors
+    }
+
+    /// Whether all examples could be loaded and produced their expected answers
+    pub fn passed(&self) -> bool {
+        self.errors.is_empty() && self.checked.iter().all(|it| it.verdict() == Verdict::Pass)
+    }
+}
+
+impl DayVisitor for ExampleRunner {
+    fn visit<D: Day + Send + 'static>(&mut self, day: usize, _puzzle: &mut D) {
+        if !(self.filter)(day) {
+            return;
+        }
+        let dir = inputs::examples_dir(self.year, day);
+        match Example::load(&dir) {
+            Ok(examples) => {
+                for example in examples {
+                    self.checked.extend(example.check::<D>(day, self.timeout));
+                }
+            }
+            Err(err) => self
+                .errors
+                .push(format!("Day {}: {}: {}", day, dir.display(), err)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Default)]
+    struct Sum(Vec<usize>);
+
+    impl Day for Sum {
+        type Result1 = usize;
+        type Result2 = usize;
+
+        fn parse(&mut self, input: &str) -> Result<(), ParseError> {
+            for line in input.lines() {
+                let n = line
+                    .parse()
+                    .map_err(|_| ParseError::at(input, line, "number"))?;
+                self.0.push(n);
+            }
+            Ok(())
+        }
+
+        fn part1(&mut self) -> Result<Self::Result1, SolveError> {
+            Ok(self.0.iter().sum())
+        }
+
+        fn part2(&mut self) -> Result<Self::Result2, SolveError> {
+            Err(SolveError::NoSolution)
+        }
+    }
+
+    #[test]
+    fn check() {
+        let example = Example::parse("1.txt", "part1: 3\n---\n1\n2\n").unwrap();
+        assert_eq!(example.input, "1\n2\n");
+        assert_eq!(example.answers.get(2), None);
+        let checked = example.check::<Sum>(1, None);
+        assert_eq!(checked.len(), 1);
+        assert_eq!(checked[0].verdict(), Verdict::Pass);
+        assert_eq!(checked[0].to_string(), "Day  1 1.txt part 1: PASS");
+
+        let example = Example::parse("2.txt", "part1: 3\npart2: 4\n---\n1\nx\n").unwrap();
+        let checked = example.check::<Sum>(1, None);
+        assert_eq!(checked[1].verdict(), Verdict::Fail);
+        assert_eq!(
+            checked[1].to_string(),
+            "Day  1 2.txt part 2: FAIL (expected 4, parse error: line 2, column 1: expected number, found \"x\")"
+        );
+
+        let example = Example::parse("3.txt", "part1:\npart2: 4\n---\n1\n").unwrap();
+        assert_eq!(example.answers.get(1), None);
+        assert_eq!(
+            example.check::<Sum>(1, None)[0].to_string(),
+            "Day  1 3.txt part 2: FAIL (expected 4, No solution found)"
+        );
+
+        assert!(Example::parse("4.txt", "part1: 3\n1\n").is_err());
+        assert!(Example::parse("5.txt", "part3: 3\n---\n1\n").is_err());
+    }
+
+    #[derive(Default)]
+    struct Faulty;
+
+    impl Day for Faulty {
+        type Result1 = usize;
+        type Result2 = usize;
+
+        fn part1(&mut self) -> Result<Self::Result1, SolveError> {
+            std::thread::sleep(Duration::from_secs(1));
+            Ok(0)
+        }
+
+        fn part2(&mut self) -> Result<Self::Result2, SolveError> {
+            panic!("Bad op")
+        }
+    }
+
+    #[test]
+    fn check_isolated() {
+        let example = Example::parse("1.txt", "part1: 0\npart2: 0\n---\n").unwrap();
+        let checked = example.check::<Faulty>(2, Some(Duration::from_millis(20)));
+        assert_eq!(
+            checked[0].to_string(),
+            "Day  2 1.txt part 1: FAIL (expected 0, Timed out after 20ms)"
+        );
+        assert_eq!(
+            checked[1].to_string(),
+            "Day  2 1.txt part 2: FAIL (expected 0, skipped after part 1 timed out)"
+        );
+
+        let example = Example::parse("2.txt", "part2: 0\n---\n").unwrap();
+        assert_eq!(
+            example.check::<Faulty>(2, None)[0].to_string(),
+            "Day  2 2.txt part 2: FAIL (expected 0, Panicked: Bad op)"
+        );
+    }
+}
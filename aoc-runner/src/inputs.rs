@@ -0,0 +1,373 @@
+//! Locates, validates and downloads puzzle inputs, examples and known answers on disk
+
+use std::{
+    fmt::Display,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+/// Returns the path of the input file of `day`, i.e. `./input/<year>/NN.txt`
+///
+/// Falls back to `./input/NN.txt` if only that one exists, or if the year is unknown.
+pub fn input_path(year: Option<usize>, day: usize) -> PathBuf {
+    resolve(Path::new("./input"), year, day)
+}
+
+/// Returns the path of the answers file of `day`, i.e. `./answers/<year>/NN.txt`
+///
+/// Falls back to `./answers/NN.txt` if only that one exists, or if the year is unknown.
+pub fn answers_path(year: Option<usize>, day: usize) -> PathBuf {
+    resolve(Path::new("./answers"), year, day)
+}
+
+/// Returns the directory holding the examples of `day`, i.e. `./examples/<year>/NN`
+///
+/// Falls back to `./examples/NN` if only that one exists, or if the year is unknown.
+pub fn examples_dir(year: Option<usize>, day: usize) -> PathBuf {
+    resolve_file(Path::new("./examples"), year, &format!("{:0>2}", day))
+}
+
+fn resolve(dir: &Path, year: Option<usize>, day: usize) -> PathBuf {
+    resolve_file(dir, year, &format!("{:0>2}.txt", day))
+}
+
+fn resolve_file(dir: &Path, year: Option<usize>, file_name: &str) -> PathBuf {
+    let fallback = dir.join(file_name);
+    let Some(year) = year else {
+        return fallback;
+    };
+    let path = dir.join(year.to_string()).join(file_name);
+    if !path.exists() && fallback.exists() {
+        fallback
+    } else {
+        path
+    }
+}
+
+/// A problem of an input that makes it differ from what puzzles expect
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputIssue {
+    Empty,
+    MissingTrailingNewline,
+    CarriageReturn,
+}
+
+impl Display for InputIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputIssue::Empty => write!(f, "empty"),
+            InputIssue::MissingTrailingNewline => write!(f, "missing trailing newline"),
+            InputIssue::CarriageReturn => write!(f, "CRLF line endings"),
+        }
+    }
+}
+
+/// Returns all issues of `input`, which is valid if there are none
+pub fn validate(input: &str) -> Vec<InputIssue> {
+    let mut issues = Vec::new();
+    if input.trim().is_empty() {
+        issues.push(InputIssue::Empty);
+        return issues;
+    }
+    if !input.ends_with('\n') {
+        issues.push(InputIssue::MissingTrailingNewline);
+    }
+    if input.contains('\r') {
+        issues.push(InputIssue::CarriageReturn);
+    }
+    issues
+}
+
+/// Fixes all issues of `input` except for it being empty
+pub fn normalize(input: &str) -> String {
+    let mut normalized = input.replace("\r\n", "\n").replace('\r', "\n");
+    if !normalized.trim().is_empty() && !normalized.ends_with('\n') {
+        normalized.push('\n');
+    }
+    normalized
+}
+
+/// The state of a day's input file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputStatus {
+    Missing,
+    Valid,
+    Invalid(Vec<InputIssue>),
+}
+
+/// Whether [InputCache::fetch] had to download an input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fetched {
+    Cached,
+    Downloaded,
+}
+
+/// Sends HTTP requests on behalf of an [InputCache]
+///
+/// Tests can replace the client, or point it at a local stand-in server via
+/// [InputCache::with_url].
+pub trait HttpClient {
+    /// Returns the body of a successful GET request to `url`, authenticated by the `session` cookie
+    fn get(&mut self, url: &str, session: &str) -> io::Result<String>;
+}
+
+/// An [HttpClient] running the `curl` command
+///
+/// The session cookie is passed on stdin rather than as argument, so other users can't read it from
+/// the process list.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Curl;
+
+impl HttpClient for Curl {
+    fn get(&mut self, url: &str, session: &str) -> io::Result<String> {
+        let mut child = Command::new("curl")
+            .args(["--silent", "--show-error", "--fail"])
+            .args(["--header", "@-"])
+            .args(["--user-agent", "o.herrmann92@gmail.com"])
+            .arg(url)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        // dropping stdin closes it, so curl stops reading headers
+        if let Some(mut stdin) = child.stdin.take() {
+            writeln!(stdin, "Cookie: session={}", session)?;
+        }
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(io::Error::other(format!("GET {}: {}", url, stderr.trim())));
+        }
+        String::from_utf8(output.stdout)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
+
+/// Manages the input files of a year, e.g. `./input/2024/NN.txt`
+///
+/// Downloads only inputs that are not yet cached on disk.
+#[derive(Debug, Clone)]
+pub struct InputCache {
+    dir: PathBuf,
+    year: Option<usize>,
+    url: String,
+}
+
+impl InputCache {
+    pub fn new(dir: impl Into<PathBuf>, year: Option<usize>) -> Self {
+        Self {
+            dir: dir.into(),
+            year,
+            url: "https://adventofcode.com".to_string(),
+        }
+    }
+
+    /// Downloads inputs from `url` instead of `https://adventofcode.com`
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.url = url.into();
+        self
+    }
+
+    /// Returns the path of the input file of `day`, see [input_path]
+    pub fn path(&self, day: usize) -> PathBuf {
+        resolve(&self.dir, self.year, day)
+    }
+
+    pub fn status(&self, day: usize) -> io::Result<InputStatus> {
+        let input = match fs::read_to_string(self.path(day)) {
+            Ok(input) => input,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(InputStatus::Missing),
+            Err(err) => return Err(err),
+        };
+        let issues = validate(&input);
+        if issues.is_empty() {
+            Ok(InputStatus::Valid)
+        } else {
+            Ok(InputStatus::Invalid(issues))
+        }
+    }
+
+    /// Normalizes the input file of `day` in place, returning whether it changed
+    pub fn normalize(&self, day: usize) -> io::Result<bool> {
+        let path = self.path(day);
+        let input = fs::read_to_string(&path)?;
+        let normalized = normalize(&input);
+        if normalized == input {
+            return Ok(false);
+        }
+        fs::write(path, normalized)?;
+        Ok(true)
+    }
+
+    /// Downloads the input of `day` using `client`, unless it is already cached
+    ///
+    /// Empty files, including whitespace-only ones, are placeholders and get replaced. Downloaded
+    /// inputs are normalized before they are stored, empty ones are rejected.
+    pub fn fetch(
+        &self,
+        day: usize,
+        client: &mut impl HttpClient,
+        session: &str,
+    ) -> io::Result<Fetched> {
+        match fs::read_to_string(self.path(day)) {
+            Ok(input) if !validate(&input).contains(&InputIssue::Empty) => {
+                return Ok(Fetched::Cached)
+            }
+            Ok(_) => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+        let Some(year) = self.year else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Cannot download inputs of an unknown year",
+            ));
+        };
+        let url = format!("{}/{}/day/{}/input", self.url, year, day);
+        let input = normalize(&client.get(&url, session)?);
+        if validate(&input).contains(&InputIssue::Empty) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("GET {}: empty input", url),
+            ));
+        }
+        let path = self.path(day);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, input)?;
+        Ok(Fetched::Downloaded)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::{TcpListener, TcpStream},
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        thread,
+    };
+
+    use super::*;
+
+    #[test]
+    fn validate_and_normalize() {
+        assert_eq!(validate("1 2\n"), vec![]);
+        assert_eq!(validate("\n"), vec![InputIssue::Empty]);
+        assert_eq!(
+            validate("1 2\r\n3 4"),
+            vec![
+                InputIssue::MissingTrailingNewline,
+                InputIssue::CarriageReturn
+            ]
+        );
+        assert_eq!(normalize("1 2\r\n3 4"), "1 2\n3 4\n");
+        assert_eq!(normalize(""), "");
+    }
+
+    /// Serves `body` as input of every day via HTTP, counting the requests
+    fn stand_in_server(body: &'static str) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(&stream);
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    line.clear();
+                }
+                counter.fetch_add(1, Ordering::SeqCst);
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        (url, requests)
+    }
+
+    /// Sends plain HTTP requests over a [TcpStream], so tests don't depend on `curl`
+    struct TcpClient;
+
+    impl HttpClient for TcpClient {
+        fn get(&mut self, url: &str, session: &str) -> io::Result<String> {
+            let url = url.strip_prefix("http://").expect("plain HTTP url");
+            let (host, path) = url.split_once('/').unwrap_or((url, ""));
+            let mut stream = TcpStream::connect(host)?;
+            write!(
+                stream,
+                "GET /{} HTTP/1.1\r\nHost: {}\r\nCookie: session={}\r\nConnection: close\r\n\r\n",
+                path, host, session
+            )?;
+            let mut response = String::new();
+            stream.read_to_string(&mut response)?;
+            let (head, body) = response.split_once("\r\n\r\n").unwrap_or((&response, ""));
+            if !head.starts_with("HTTP/1.1 200") {
+                return Err(io::Error::other(format!("GET {}: {}", url, head)));
+            }
+            Ok(body.to_string())
+        }
+    }
+
+    #[test]
+    fn fetch() {
+        let dir = std::env::temp_dir().join(format!("aoc-runner-inputs-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let (url, requests) = stand_in_server("1 2\r\n3 4");
+        let cache = InputCache::new(&dir, Some(2024)).with_url(url);
+
+        assert_eq!(cache.status(1).unwrap(), InputStatus::Missing);
+        assert_eq!(
+            cache.fetch(1, &mut TcpClient, "").unwrap(),
+            Fetched::Downloaded
+        );
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+        assert_eq!(cache.status(1).unwrap(), InputStatus::Valid);
+        assert_eq!(
+            fs::read_to_string(dir.join("2024/01.txt")).unwrap(),
+            "1 2\n3 4\n"
+        );
+
+        // cached days are never downloaded again
+        assert_eq!(cache.fetch(1, &mut TcpClient, "").unwrap(), Fetched::Cached);
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+        // placeholders are replaced
+        fs::write(dir.join("2024/03.txt"), "").unwrap();
+        assert_eq!(
+            cache.fetch(3, &mut TcpClient, "").unwrap(),
+            Fetched::Downloaded
+        );
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+        fs::write(dir.join("2024/04.txt"), "\n").unwrap();
+        assert_eq!(
+            cache.fetch(4, &mut TcpClient, "").unwrap(),
+            Fetched::Downloaded
+        );
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+
+        fs::write(dir.join("2024/02.txt"), "5 6").unwrap();
+        assert_eq!(cache.fetch(2, &mut TcpClient, "").unwrap(), Fetched::Cached);
+        assert_eq!(
+            cache.status(2).unwrap(),
+            InputStatus::Invalid(vec![InputIssue::MissingTrailingNewline])
+        );
+        assert!(cache.normalize(2).unwrap());
+        assert!(!cache.normalize(2).unwrap());
+        assert_eq!(cache.status(2).unwrap(), InputStatus::Valid);
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
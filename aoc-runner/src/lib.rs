@@ -1,7 +1,29 @@
 /// Scaffold methods for an AoC runner
-
 pub mod analyzer;
+pub mod answer;
+pub mod baseline;
 pub mod day;
+pub mod error;
+pub mod examples;
+pub mod inputs;
+pub mod memory;
+pub mod params;
+pub mod parse;
+pub mod report;
+pub mod run;
+pub mod runner;
+pub mod verify;
 
-pub use analyzer::{Analyzer, TimeAnalyzer};
-pub use day::Day;
+pub use analyzer::{Analyzer, InputInfo, Outcome, TimeAnalyzer};
+pub use answer::Answer;
+pub use baseline::Baseline;
+pub use day::{Day, DayVisitor};
+pub use error::{ParamError, ParseError, SolveError};
+pub use examples::{Example, ExampleRunner};
+pub use memory::MemoryAnalyzer;
+pub use params::Params;
+pub use parse::ParseSlice;
+pub use report::{Format, MemoryReport, Report};
+pub use run::ParallelAnalyzer;
+pub use runner::Runner;
+pub use verify::{Answers, Verdict};
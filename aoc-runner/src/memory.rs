@@ -0,0 +1,245 @@
+//! Tracks allocations of the global allocator while parsing and running parts
+//!
+//! Allocations are only counted if the binary installs [CountingAllocator] as its global allocator:
+//!
+//! ```no_run
+//! use aoc_runner::memory::CountingAllocator;
+//!
+//! #[global_allocator]
+//! static ALLOCATOR: CountingAllocator = CountingAllocator;
+//! ```
+//!
+//! The allocator only counts once a [MemoryAnalyzer] starts measuring, so runs without one don't
+//! pay for counting.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    collections::BTreeMap,
+    sync::atomic::{AtomicBool, AtomicIsize, AtomicUsize, Ordering},
+};
+
+use crate::{
+    analyzer::{InputInfo, Outcome},
+    report::MemoryReport,
+    run::ParallelAnalyzer,
+    Analyzer, Format, ParseError,
+};
+
+/// Whether allocations are counted
+static COUNTING: AtomicBool = AtomicBool::new(false);
+/// Number of allocations made so far
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+/// Bytes currently allocated, relative to when counting started
+///
+/// Freeing memory allocated before may turn it negative.
+static CURRENT: AtomicIsize = AtomicIsize::new(0);
+/// Maximum of bytes allocated at once since the last reset
+static PEAK: AtomicIsize = AtomicIsize::new(0);
+
+/// A global allocator counting the allocations and bytes of the [System] allocator
+///
+/// Counts nothing until [CountingAllocator::enable] is called.
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    /// Starts counting allocations for the rest of the process
+    pub fn enable() {
+        COUNTING.store(true, Ordering::Relaxed);
+    }
+
+    fn allocated(size: usize) {
+        let current = CURRENT.fetch_add(size as isize, Ordering::Relaxed) + size as isize;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() && COUNTING.load(Ordering::Relaxed) {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            Self::allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() && COUNTING.load(Ordering::Relaxed) {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            Self::allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        if COUNTING.load(Ordering::Relaxed) {
+            CURRENT.fetch_sub(layout.size() as isize, Ordering::Relaxed);
+        }
+    }
+
+    /// Counts a reallocation as allocation, as growing a collection usually moves it
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() && COUNTING.load(Ordering::Relaxed) {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            CURRENT.fetch_sub(layout.size() as isize, Ordering::Relaxed);
+            Self::allocated(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Allocations made while parsing or running a part
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Usage {
+    pub allocations: usize,
+    /// Maximum of bytes allocated at once on top of the memory allocated before
+    pub peak_bytes: usize,
+}
+
+/// Measures the allocations of each parse and part
+///
+/// The counters are global, so allocations of other threads are counted as well. Only the first
+/// sample of repeated runs is kept. Enables counting of the [CountingAllocator] when it starts
+/// measuring.
+///
+/// Prints a [MemoryReport] after all days, or after the day if only a single day is run. Combined
+/// with a [TimeAnalyzer][crate::TimeAnalyzer] as `(MemoryAnalyzer, TimeAnalyzer)`, the report
+/// follows the timings. Counting stays enabled once started, so the timings include its overhead.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryAnalyzer {
+    /// Allocations and bytes allocated when the current step started
+    start: (usize, isize),
+    /// Usage per (day, part), where part 0 denotes parsing
+    usage: BTreeMap<(usize, usize), Usage>,
+    format: Format,
+    /// Whether all days are run, so the report is printed after all of them
+    all: bool,
+}
+
+impl MemoryAnalyzer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the format the report is printed in
+    pub fn with_format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Collects the measured allocations into a [MemoryReport]
+    pub fn report(&self) -> MemoryReport {
+        let mut days: Vec<usize> = self.usage.keys().map(|(day, _)| *day).collect();
+        days.dedup();
+        let days = days
+            .into_iter()
+            .map(|day| (day, [0, 1, 2].map(|part| self.usage(day, part))))
+            .collect();
+        MemoryReport { days }
+    }
+
+    fn print_report(&self) {
+        self.report()
+            .write(self.format, &mut std::io::stdout().lock())
+            .expect("Could not write report");
+    }
+
+    /// The usage of `part` of `day`, where part 0 denotes parsing
+    pub fn usage(&self, day: usize, part: usize) -> Option<Usage> {
+        self.usage.get(&(day, part)).cloned()
+    }
+
+    fn start(&mut self) {
+        CountingAllocator::enable();
+        let current = CURRENT.load(Ordering::Relaxed);
+        PEAK.store(current, Ordering::Relaxed);
+        self.start = (ALLOCATIONS.load(Ordering::Relaxed), current);
+    }
+
+    fn stop(&mut self, day: usize, part: usize) {
+        let usage = Usage {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed) - self.start.0,
+            peak_bytes: (PEAK.load(Ordering::Relaxed) - self.start.1).max(0) as usize,
+        };
+        self.usage.entry((day, part)).or_insert(usage);
+    }
+}
+
+impl Analyzer for MemoryAnalyzer {
+    fn before_all(&mut self) {
+        self.all = true;
+    }
+
+    fn after_all(&mut self) {
+        self.print_report();
+    }
+
+    fn after_day(&mut self, _day: usize) {
+        if !self.all {
+            self.print_report();
+        }
+    }
+
+    fn verbose(&self) -> bool {
+        // keep machine-readable output free of progress prints
+        self.format == Format::Table
+    }
+
+    fn before_parse(&mut self, _day: usize, _input: &InputInfo) {
+        self.start();
+    }
+
+    fn after_parse(&mut self, day: usize, _result: Result<(), &ParseError>) {
+        self.stop(day, 0);
+    }
+
+    fn before_part(&mut self, _day: usize, _part: usize) {
+        self.start();
+    }
+
+    fn after_part(&mut self, day: usize, part: usize, _outcome: Outcome) {
+        self.stop(day, part);
+    }
+}
+
+impl ParallelAnalyzer for MemoryAnalyzer {
+    fn fork(&self) -> Self {
+        Self {
+            // a started run keeps the forks from reporting single days
+            all: self.all,
+            format: self.format,
+            ..Self::default()
+        }
+    }
+
+    fn merge(&mut self, other: Self) {
+        self.usage.extend(other.usage);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    #[test]
+    fn memory_analyzer() {
+        let mut analyzer = MemoryAnalyzer::new();
+        analyzer.before_part(1, 1);
+        let mut v = Vec::with_capacity(1000);
+        v.extend(0..2000_usize);
+        drop(v);
+        analyzer.after_part(1, 1, Outcome::Solved(&1.into()));
+
+        // other tests may allocate concurrently
+        let usage = analyzer.usage(1, 1).unwrap();
+        assert!(usage.allocations >= 2);
+        assert!(usage.peak_bytes >= 2000 * size_of::<usize>());
+        assert_eq!(analyzer.usage(1, 0), None);
+    }
+}
@@ -0,0 +1,117 @@
+//! Parameters overriding a puzzle's defaults, e.g. the grid size of an example
+
+use std::{collections::BTreeMap, str::FromStr};
+
+use crate::error::ParamError;
+
+/// Prefix of an input's first line holding parameters, e.g. `#!aoc width=11 height=7`
+pub const HEADER: &str = "#!aoc";
+
+/// Parameters of a puzzle, given as `name=value` pairs
+///
+/// Each puzzle keeps its parameters as typed fields with defaults for the real inputs and overrides
+/// them in [Day::configure][crate::Day::configure].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params(BTreeMap<String, String>);
+
+impl Params {
+    /// Splits an optional parameter header off the first line of `input`
+    ///
+    /// Returns the parameters along with the remaining input.
+    pub fn split_header(input: &str) -> Result<(Self, &str), ParamError> {
+        let Some(rest) = input.strip_prefix(HEADER) else {
+            return Ok((Self::default(), input));
+        };
+        let (header, input) = rest.split_once('\n').unwrap_or((rest, ""));
+        Ok((header.parse()?, input))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Adds all parameters of `other`, overriding parameters of the same name
+    pub fn merge(&mut self, other: &Params) {
+        self.0
+            .extend(other.0.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
+
+    /// Returns the parameter `name` parsed as `T`, if given
+    pub fn get<T: FromStr>(&self, name: &str) -> Result<Option<T>, ParamError> {
+        let Some(value) = self.0.get(name) else {
+            return Ok(None);
+        };
+        let parsed = value.parse().map_err(|_| ParamError::InvalidValue {
+            name: name.to_string(),
+            value: value.clone(),
+        })?;
+        Ok(Some(parsed))
+    }
+
+    /// Overrides `value` by the parameter `name`, if given
+    pub fn apply<T: FromStr>(&self, name: &str, value: &mut T) -> Result<(), ParamError> {
+        if let Some(parsed) = self.get(name)? {
+            *value = parsed;
+        }
+        Ok(())
+    }
+
+    /// Fails if any parameter is not one of `names`
+    pub fn expect(&self, names: &[&str]) -> Result<(), ParamError> {
+        match self.0.keys().find(|name| !names.contains(&name.as_str())) {
+            Some(name) => Err(ParamError::Unknown {
+                name: name.clone(),
+                valid: names.iter().map(|it| it.to_string()).collect(),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl FromStr for Params {
+    type Err = ParamError;
+
+    /// Parses `name=value` pairs separated by whitespace or commas
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(|ch: char| ch.is_whitespace() || ch == ',')
+            .filter(|it| !it.is_empty())
+            .map(|param| match param.split_once('=') {
+                Some((name, value)) if !name.is_empty() => {
+                    Ok((name.to_string(), value.to_string()))
+                }
+                _ => Err(ParamError::Malformed(param.to_string())),
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn params() {
+        let (mut params, input) = Params::split_header("#!aoc width=11 height=7\n1,2\n").unwrap();
+        assert_eq!(input, "1,2\n");
+        assert_eq!(params.get::<usize>("width"), Ok(Some(11)));
+        assert_eq!(params.get::<usize>("depth"), Ok(None));
+
+        params.merge(&"height=x".parse().unwrap());
+        let mut height = 103_usize;
+        assert_eq!(
+            params.apply("height", &mut height).unwrap_err().to_string(),
+            "Invalid value of parameter height: x"
+        );
+        assert_eq!(
+            params.expect(&["width"]).unwrap_err().to_string(),
+            "Unknown parameter: height. Valid parameters are: width"
+        );
+        assert!(params.expect(&["width", "height"]).is_ok());
+
+        let (params, input) = Params::split_header("1,2\n").unwrap();
+        assert!(params.is_empty());
+        assert_eq!(input, "1,2\n");
+        assert!("width".parse::<Params>().is_err());
+    }
+}
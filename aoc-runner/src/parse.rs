@@ -0,0 +1,78 @@
+//! Helpers for fallible parsing of a puzzle's input
+
+use std::str::FromStr;
+
+use crate::ParseError;
+
+/// Fallible operations on a slice of a puzzle's input
+///
+/// All operations take the whole `input` the slice was taken from, so errors can report the
+/// position of the offending part.
+pub trait ParseSlice<'a> {
+    /// Parses this slice into `T`
+    fn parse_at<T: FromStr>(self, input: &str) -> Result<T, ParseError>;
+
+    /// Splits this slice at the first occurrence of `delimiter`
+    fn split_once_at(self, input: &str, delimiter: &str) -> Result<(&'a str, &'a str), ParseError>;
+
+    /// Returns this slice with `prefix` removed
+    fn strip_prefix_at(self, input: &str, prefix: &str) -> Result<&'a str, ParseError>;
+}
+
+impl<'a> ParseSlice<'a> for &'a str {
+    fn parse_at<T: FromStr>(self, input: &str) -> Result<T, ParseError> {
+        self.parse()
+            .map_err(|_| ParseError::at(input, self, std::any::type_name::<T>()))
+    }
+
+    fn split_once_at(self, input: &str, delimiter: &str) -> Result<(&'a str, &'a str), ParseError> {
+        self.split_once(delimiter)
+            .ok_or_else(|| ParseError::at(input, self, format!("{:?}", delimiter)))
+    }
+
+    fn strip_prefix_at(self, input: &str, prefix: &str) -> Result<&'a str, ParseError> {
+        self.strip_prefix(prefix)
+            .ok_or_else(|| ParseError::at(input, self, format!("{:?}", prefix)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reports_position_of_slice() {
+        let input = "1 2\n3 x\n";
+        let x = input
+            .lines()
+            .nth(1)
+            .unwrap()
+            .split_whitespace()
+            .nth(1)
+            .unwrap();
+        let err = x.parse_at::<u32>(input).unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 3));
+        assert_eq!(err.expected(), "u32");
+        assert_eq!(err.found(), Some("x"));
+    }
+
+    #[test]
+    fn reports_end_of_input() {
+        let input = "a\nb";
+        let err = ParseError::end_of_input(input, "another line").with_day(3);
+        assert_eq!(
+            err.to_string(),
+            "Day 3: line 2, column 2: expected another line, found end of input"
+        );
+    }
+
+    #[test]
+    fn reports_missing_delimiter() {
+        let input = "ab\r\n\r\ncd";
+        let err = input.split_once_at(input, "\n\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 1: expected \"\\n\\n\", found \"ab\""
+        );
+    }
+}
@@ -0,0 +1,598 @@
+//! Reports of a run's timings and answers, writable in several formats
+
+use std::{fmt::Display, io::Write, str::FromStr, time::Duration};
+
+use crate::{baseline::Delta, memory::Usage, Answer, Answers, Verdict};
+
+/// Output format of a [Report]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Human readable table with auto-scaled units
+    #[default]
+    Table,
+    /// JSON document with durations in nanoseconds
+    Json,
+    /// CSV with one row per day and durations in nanoseconds
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!(
+                "Invalid format: {}. Valid formats are: table,json,csv",
+                s
+            )),
+        }
+    }
+}
+
+/// Timings and answers of a single day
+#[derive(Debug, Clone, Default)]
+pub struct DayReport {
+    pub day: usize,
+    pub parse: Option<Duration>,
+    pub parts: [Option<Duration>; 2],
+    pub total: Option<Duration>,
+    /// Whether parsing (index 0) or a part (index 1 and 2) failed
+    pub failed: [bool; 3],
+    pub answers: Answers,
+    pub verdicts: [Option<Verdict>; 2],
+    /// Changes of parse (index 0) and part (index 1 and 2) timings relative to a baseline
+    pub deltas: [Option<Delta>; 3],
+    /// Statistics over the samples of parse (index 0) and part (index 1 and 2) timings
+    pub stats: [Option<Stats>; 3],
+}
+
+/// Statistics over the repeated samples of a timing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Calculates the statistics over `samples`, returning `None` if there are none
+    pub fn new(samples: &[Duration]) -> Option<Self> {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let min = *sorted.first()?;
+        let mid = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[mid - 1] + sorted[mid]) / 2
+        } else {
+            sorted[mid]
+        };
+        let mean = sorted.iter().sum::<Duration>() / sorted.len() as u32;
+        let variance = sorted
+            .iter()
+            .map(|it| (it.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / sorted.len() as f64;
+        Some(Self {
+            samples: sorted.len(),
+            min,
+            median,
+            mean,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+/// Summed up timings of all days
+#[derive(Debug, Clone, Default)]
+pub struct TotalReport {
+    pub parse: Duration,
+    pub parts: [Duration; 2],
+    pub total: Duration,
+}
+
+/// Timings and answers of a run
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    pub total: TotalReport,
+    pub days: Vec<DayReport>,
+}
+
+impl Report {
+    /// Writes this report to `out` in the given `format`
+    pub fn write(&self, format: Format, out: &mut impl Write) -> std::io::Result<()> {
+        match format {
+            Format::Table => self.write_table(out),
+            Format::Json => self.write_json(out),
+            Format::Csv => self.write_csv(out),
+        }
+    }
+
+    fn write_table(&self, out: &mut impl Write) -> std::io::Result<()> {
+        // a step without timing never ran, e.g. as parsing or an earlier part failed
+        let cell = |failed: bool, duration: Option<Duration>| match (failed, duration) {
+            (true, _) => Cell::Failed,
+            (false, Some(duration)) => Cell::Time(duration),
+            (false, None) => Cell::Text("SKIPPED".to_string()),
+        };
+
+        // only show verification and delta columns if any answers were verified or baselines known
+        let checks = self
+            .days
+            .iter()
+            .any(|day| day.verdicts.iter().any(Option::is_some));
+        let deltas = self
+            .days
+            .iter()
+            .any(|day| day.deltas.iter().any(Option::is_some));
+        let mut columns = vec!["Parse", "Part 1", "Part 2", "Total"];
+        if checks {
+            columns.extend(["Check 1", "Check 2"]);
+        }
+        if deltas {
+            columns.extend(["Δ Parse", "Δ Part 1", "Δ Part 2"]);
+        }
+        let border = write_header(out, &columns)?;
+        let mut cells = vec![
+            Cell::Time(self.total.parse),
+            Cell::Time(self.total.parts[0]),
+            Cell::Time(self.total.parts[1]),
+            Cell::Time(self.total.total),
+        ];
+        if checks {
+            for part in [1, 2] {
+                let verdicts = self.days.iter().filter_map(|day| day.verdicts[part - 1]);
+                let passed = verdicts.clone().filter(|v| *v == Verdict::Pass).count();
+                cells.push(Cell::Text(format!("{}/{}", passed, verdicts.count())));
+            }
+        }
+        if deltas {
+            cells.extend([0, 1, 2].map(|_| Cell::Text(String::new())));
+        }
+        write_line(out, "Total", &cells)?;
+        writeln!(out, "+{}+", "-".repeat(border.len() - 2))?;
+        for day in self.days.iter() {
+            let mut cells = vec![
+                cell(day.failed[0], day.parse),
+                cell(day.failed[1], day.parts[0]),
+                cell(day.failed[2], day.parts[1]),
+                Cell::Time(day.total.unwrap_or_default()),
+            ];
+            if checks {
+                for verdict in day.verdicts {
+                    cells.push(Cell::Text(verdict.unwrap_or(Verdict::Unknown).to_string()));
+                }
+            }
+            if deltas {
+                for delta in day.deltas {
+                    cells.push(Cell::Text(
+                        delta.map(|it| it.to_string()).unwrap_or_default(),
+                    ));
+                }
+            }
+            write_line(out, day.day, &cells)?;
+        }
+        writeln!(out, "{}", border)?;
+
+        // only show statistics if timings were sampled repeatedly
+        if self.repeated() {
+            let columns = ["Step", "Samples", "Min", "Median", "Mean", "Stddev"];
+            let border = write_header(out, &columns)?;
+            for day in self.days.iter() {
+                for (step, stats) in ["Parse", "Part 1", "Part 2"].into_iter().zip(day.stats) {
+                    let Some(stats) = stats else {
+                        continue;
+                    };
+                    let cells = [
+                        Cell::Text(step.to_string()),
+                        Cell::Text(stats.samples.to_string()),
+                        Cell::Time(stats.min),
+                        Cell::Time(stats.median),
+                        Cell::Time(stats.mean),
+                        Cell::Time(stats.stddev),
+                    ];
+                    write_line(out, day.day, &cells)?;
+                }
+            }
+            writeln!(out, "{}", border)?;
+        }
+
+        Ok(())
+    }
+
+    /// Whether any timing was sampled more than once
+    fn repeated(&self) -> bool {
+        self.days
+            .iter()
+            .flat_map(|day| day.stats)
+            .any(|stats| stats.is_some_and(|it| it.samples > 1))
+    }
+
+    fn write_json(&self, out: &mut impl Write) -> std::io::Result<()> {
+        writeln!(out, "{{")?;
+        writeln!(
+            out,
+            "  \"total\": {{\"parse_ns\": {}, \"part1_ns\": {}, \"part2_ns\": {}, \"total_ns\": {}}},",
+            self.total.parse.as_nanos(),
+            self.total.parts[0].as_nanos(),
+            self.total.parts[1].as_nanos(),
+            self.total.total.as_nanos(),
+        )?;
+        writeln!(out, "  \"days\": [")?;
+        for (idx, day) in self.days.iter().enumerate() {
+            let separator = if idx + 1 < self.days.len() { "," } else { "" };
+            writeln!(
+                out,
+                "    {{\"day\": {}, \"parse_ns\": {}, \"part1_ns\": {}, \"part2_ns\": {}, \"total_ns\": {}, \
+                \"part1\": {}, \"part2\": {}, \"part1_check\": {}, \"part2_check\": {}, \"failed\": [{}]{}}}{}",
+                day.day,
+                json_nanos(day.parse),
+                json_nanos(day.parts[0]),
+                json_nanos(day.parts[1]),
+                json_nanos(day.total),
+                json_answer(day.answers.get(1)),
+                json_answer(day.answers.get(2)),
+                json_string(day.verdicts[0].map(|it| it.to_string()).as_deref()),
+                json_string(day.verdicts[1].map(|it| it.to_string()).as_deref()),
+                day.failed_steps().map(|it| format!("\"{}\"", it)).collect::<Vec<_>>().join(", "),
+                if self.repeated() { format!(", \"stats\": {}", day.json_stats()) } else { String::new() },
+                separator
+            )?;
+        }
+        writeln!(out, "  ]")?;
+        writeln!(out, "}}")
+    }
+
+    fn write_csv(&self, out: &mut impl Write) -> std::io::Result<()> {
+        writeln!(
+            out,
+            "day,parse_ns,part1_ns,part2_ns,total_ns,part1,part2,part1_check,part2_check,failed"
+        )?;
+        for day in self.days.iter() {
+            let nanos =
+                |d: Option<Duration>| d.map(|it| it.as_nanos().to_string()).unwrap_or_default();
+            let fields = [
+                day.day.to_string(),
+                nanos(day.parse),
+                nanos(day.parts[0]),
+                nanos(day.parts[1]),
+                nanos(day.total),
+                day.answers
+                    .get(1)
+                    .map(Answer::to_string)
+                    .unwrap_or_default(),
+                day.answers
+                    .get(2)
+                    .map(Answer::to_string)
+                    .unwrap_or_default(),
+                day.verdicts[0].map(|it| it.to_string()).unwrap_or_default(),
+                day.verdicts[1].map(|it| it.to_string()).unwrap_or_default(),
+                day.failed_steps().collect::<Vec<_>>().join(";"),
+            ];
+            let line = fields
+                .iter()
+                .map(|it| csv_field(it))
+                .collect::<Vec<_>>()
+                .join(",");
+            writeln!(out, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+impl DayReport {
+    /// Statistics of the parse and part timings as JSON object
+    fn json_stats(&self) -> String {
+        let steps = ["parse", "part1", "part2"].into_iter().zip(self.stats).map(|(step, stats)| {
+            let stats = stats.map_or_else(
+                || "null".to_string(),
+                |it| {
+                    format!(
+                        "{{\"samples\": {}, \"min_ns\": {}, \"median_ns\": {}, \"mean_ns\": {}, \"stddev_ns\": {}}}",
+                        it.samples,
+                        it.min.as_nanos(),
+                        it.median.as_nanos(),
+                        it.mean.as_nanos(),
+                        it.stddev.as_nanos()
+                    )
+                },
+            );
+            format!("\"{}\": {}", step, stats)
+        });
+        format!("{{{}}}", steps.collect::<Vec<_>>().join(", "))
+    }
+
+    /// Names of the steps (parse, part1, part2) that failed
+    fn failed_steps(&self) -> impl Iterator<Item = &'static str> + '_ {
+        ["parse", "part1", "part2"]
+            .into_iter()
+            .zip(self.failed)
+            .filter_map(|(step, failed)| failed.then_some(step))
+    }
+}
+
+/// Allocations of each day's parse and parts, as measured by a [MemoryAnalyzer][crate::MemoryAnalyzer]
+#[derive(Debug, Clone, Default)]
+pub struct MemoryReport {
+    /// Allocations of parse (index 0) and parts (index 1 and 2) of each day
+    pub days: Vec<(usize, [Option<Usage>; 3])>,
+}
+
+impl MemoryReport {
+    /// Writes this report to `out` in the given `format`, as a document of its own
+    pub fn write(&self, format: Format, out: &mut impl Write) -> std::io::Result<()> {
+        const STEPS: [(&str, &str); 3] =
+            [("Parse", "parse"), ("Part 1", "part1"), ("Part 2", "part2")];
+        match format {
+            Format::Table => {
+                let border = write_header(out, &["Step", "Allocs", "Peak"])?;
+                for (day, usages) in self.days.iter() {
+                    for ((step, _), usage) in STEPS.into_iter().zip(usages) {
+                        let Some(usage) = usage else {
+                            continue;
+                        };
+                        let cells = [
+                            Cell::Text(step.to_string()),
+                            Cell::Text(usage.allocations.to_string()),
+                            Cell::Text(bytes(usage.peak_bytes)),
+                        ];
+                        write_line(out, day, &cells)?;
+                    }
+                }
+                writeln!(out, "{}", border)
+            }
+            Format::Json => {
+                writeln!(out, "{{")?;
+                writeln!(out, "  \"memory\": [")?;
+                for (idx, (day, usages)) in self.days.iter().enumerate() {
+                    let separator = if idx + 1 < self.days.len() { "," } else { "" };
+                    let steps = STEPS.into_iter().zip(usages).map(|((_, step), usage)| {
+                        let usage = usage.map_or_else(
+                            || "null".to_string(),
+                            |it| {
+                                format!(
+                                    "{{\"allocations\": {}, \"peak_bytes\": {}}}",
+                                    it.allocations, it.peak_bytes
+                                )
+                            },
+                        );
+                        format!(", \"{}\": {}", step, usage)
+                    });
+                    writeln!(
+                        out,
+                        "    {{\"day\": {}{}}}{}",
+                        day,
+                        steps.collect::<String>(),
+                        separator
+                    )?;
+                }
+                writeln!(out, "  ]")?;
+                writeln!(out, "}}")
+            }
+            Format::Csv => {
+                writeln!(out, "day,step,allocations,peak_bytes")?;
+                for (day, usages) in self.days.iter() {
+                    for ((_, step), usage) in STEPS.into_iter().zip(usages) {
+                        if let Some(usage) = usage {
+                            writeln!(
+                                out,
+                                "{},{},{},{}",
+                                day, step, usage.allocations, usage.peak_bytes
+                            )?;
+                        }
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A cell of a table
+enum Cell {
+    Time(Duration),
+    Failed,
+    Text(String),
+}
+
+fn write_line(out: &mut impl Write, day: impl Display, cells: &[Cell]) -> std::io::Result<()> {
+    write!(out, "| {:>6} |", day)?;
+    for cell in cells {
+        write_col(out, cell)?;
+    }
+    writeln!(out)
+}
+
+/// Writes the header of a table with the given columns, returning its border
+fn write_header(out: &mut impl Write, columns: &[&str]) -> std::io::Result<String> {
+    let border = format!("+--------{}+", "|----------".repeat(columns.len()));
+    let header = columns.iter().fold(String::from("| Day    |"), |acc, col| {
+        format!("{} {:<8} |", acc, col)
+    });
+    writeln!(out)?;
+    writeln!(out, "{}", border)?;
+    writeln!(out, "{}", header)?;
+    writeln!(out, "{}", border)?;
+    Ok(border)
+}
+
+fn write_col(out: &mut impl Write, cell: &Cell) -> std::io::Result<()> {
+    let duration = match cell {
+        Cell::Failed => return write!(out, " {:>8} |", "FAILED"),
+        Cell::Text(text) => return write!(out, " {:>8} |", text),
+        Cell::Time(duration) => duration,
+    };
+    if duration.as_secs() > 0 {
+        write!(out, " {:>7}s |", duration.as_secs())
+    } else if duration.as_millis() > 0 {
+        write!(out, " {:>6}ms |", duration.as_millis())
+    } else if duration.as_micros() > 0 {
+        write!(out, " {:>6}μs |", duration.as_micros())
+    } else {
+        write!(out, " {:>6}ns |", duration.as_nanos())
+    }
+}
+
+fn json_nanos(duration: Option<Duration>) -> String {
+    duration.map_or_else(|| "null".to_string(), |it| it.as_nanos().to_string())
+}
+
+/// Formats `bytes` with a binary unit, e.g. `12KiB`
+fn bytes(bytes: usize) -> String {
+    match bytes {
+        b if b >= 1 << 30 => format!("{}GiB", b >> 30),
+        b if b >= 1 << 20 => format!("{}MiB", b >> 20),
+        b if b >= 1 << 10 => format!("{}KiB", b >> 10),
+        b => format!("{}B", b),
+    }
+}
+
+/// An answer as JSON value, with integers as numbers and lists as arrays
+fn json_answer(answer: Option<&Answer>) -> String {
+    match answer {
+        None => "null".to_string(),
+        Some(Answer::Int(n)) => n.to_string(),
+        Some(Answer::Str(s)) => json_string(Some(s)),
+        Some(Answer::List(items)) => {
+            let items = items.iter().map(|it| json_answer(Some(it)));
+            format!("[{}]", items.collect::<Vec<_>>().join(", "))
+        }
+    }
+}
+
+fn json_string(s: Option<&str>) -> String {
+    let Some(s) = s else {
+        return "null".to_string();
+    };
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            ch if ch.is_control() => escaped.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => escaped.push(ch),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn report() -> Report {
+        let mut answers = Answers::default();
+        answers.set(1, "4,6,3");
+        Report {
+            total: TotalReport {
+                parse: Duration::from_nanos(10),
+                parts: [Duration::from_nanos(20), Duration::from_nanos(30)],
+                total: Duration::from_nanos(70),
+            },
+            days: vec![DayReport {
+                day: 17,
+                parse: Some(Duration::from_nanos(10)),
+                parts: [
+                    Some(Duration::from_nanos(20)),
+                    Some(Duration::from_nanos(30)),
+                ],
+                total: Some(Duration::from_nanos(70)),
+                failed: [false, false, true],
+                answers,
+                verdicts: [Some(Verdict::Pass), None],
+                deltas: [None; 3],
+                stats: [None; 3],
+            }],
+        }
+    }
+
+    #[test]
+    fn write_csv() {
+        let mut out = Vec::new();
+        report().write(Format::Csv, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "day,parse_ns,part1_ns,part2_ns,total_ns,part1,part2,part1_check,part2_check,failed\n\
+             17,10,20,30,70,\"4,6,3\",,PASS,,part2\n"
+        );
+    }
+
+    #[test]
+    fn write_json() {
+        let mut out = Vec::new();
+        report().write(Format::Json, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "{\n  \"total\": {\"parse_ns\": 10, \"part1_ns\": 20, \"part2_ns\": 30, \"total_ns\": 70},\n  \"days\": [\n    \
+             {\"day\": 17, \"parse_ns\": 10, \"part1_ns\": 20, \"part2_ns\": 30, \"total_ns\": 70, \"part1\": [4, 6, 3], \
+             \"part2\": null, \"part1_check\": \"PASS\", \"part2_check\": null, \"failed\": [\"part2\"]}\n  ]\n}\n"
+        );
+    }
+
+    #[test]
+    fn write_table() {
+        let mut report = report();
+        report.days[0].failed = [true, false, false];
+        report.days[0].parts = [None; 2];
+        let mut out = Vec::new();
+        report.write(Format::Table, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains(
+            "|     17 |   FAILED |  SKIPPED |  SKIPPED |     70ns |     PASS |  UNKNOWN |\n"
+        ));
+    }
+
+    #[test]
+    fn write_memory() {
+        let usage = Usage {
+            allocations: 3,
+            peak_bytes: 2048,
+        };
+        let report = MemoryReport {
+            days: vec![(5, [Some(usage), None, Some(usage)])],
+        };
+        let mut out = Vec::new();
+        report.write(Format::Csv, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "day,step,allocations,peak_bytes\n5,parse,3,2048\n5,part2,3,2048\n"
+        );
+
+        let mut out = Vec::new();
+        report.write(Format::Json, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "{\n  \"memory\": [\n    {\"day\": 5, \"parse\": {\"allocations\": 3, \"peak_bytes\": 2048}, \
+             \"part1\": null, \"part2\": {\"allocations\": 3, \"peak_bytes\": 2048}}\n  ]\n}\n"
+        );
+    }
+
+    #[test]
+    fn stats() {
+        let samples = [4, 1, 3, 2].map(Duration::from_micros);
+        let stats = Stats::new(&samples).unwrap();
+        assert_eq!(stats.samples, 4);
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_nanos(2500));
+        assert_eq!(stats.mean, Duration::from_nanos(2500));
+        assert_eq!(stats.stddev.as_nanos(), 1118);
+        assert_eq!(Stats::new(&[]), None);
+    }
+}
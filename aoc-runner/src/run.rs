@@ -0,0 +1,465 @@
+//! Runs a day's puzzle, sequentially or in parallel with other days
+
+use std::{
+    any::Any,
+    cell::Cell,
+    io::{self, Write},
+    panic::{self, AssertUnwindSafe},
+    sync::{mpsc, Once},
+    thread,
+    time::Duration,
+};
+
+use rayon::prelude::*;
+
+use crate::{
+    analyzer::{InputInfo, Outcome},
+    Analyzer, Answer, Answers, Day, Params, ParseError, SolveError,
+};
+
+/// An analyzer that can be split into per-day instances to analyze days in parallel
+pub trait ParallelAnalyzer: Analyzer + Send {
+    /// Creates an analyzer for a single day, to be run on another thread
+    fn fork(&self) -> Self;
+
+    /// Merges the results of an analyzer created by [ParallelAnalyzer::fork] into this one
+    fn merge(&mut self, other: Self);
+}
+
+/// Runs one or both parts of `puzzle`, titled `title`, printing its progress to `out` and errors to `err`
+///
+/// Parses and runs the parts [Analyzer::repeat] times, each time on a fresh `Default` instance.
+/// The instance is configured by the input's parameter header, overridden by `params`. Skips the
+/// day's parts if its parameters are invalid or its input cannot be parsed.
+///
+/// Panics of parsing and parts are caught and reported as failures. A part running longer than
+/// [Analyzer::timeout] fails as well and skips the day's remaining parts and samples.
+#[allow(clippy::too_many_arguments)]
+pub fn run_puzzle<D: Day + Send + 'static>(
+    puzzle: &mut D,
+    day: usize,
+    title: Option<&str>,
+    part: Option<usize>,
+    params: &Params,
+    input: &str,
+    analyzer: &mut impl Analyzer,
+    out: &mut dyn Write,
+    err: &mut dyn Write,
+) -> io::Result<Answers> {
+    let parts = match part {
+        Some(part @ (1 | 2)) => vec![part],
+        None => vec![1, 2],
+        Some(part) => panic!("Invalid part: {}. Valid parts are: 1,2", part),
+    };
+
+    let mut answers = Answers::default();
+    if analyzer.verbose() {
+        match title {
+            Some(title) => writeln!(out, "Day {}: {}", day, title)?,
+            None => writeln!(out, "Day {}", day)?,
+        }
+    }
+
+    analyzer.before_day(day);
+    let header = Params::split_header(input);
+    let input = header.as_ref().map_or(input, |(_, input)| input);
+    let info = InputInfo::new(input);
+    for sample in 0..analyzer.repeat().max(1) {
+        // every sample runs on a fresh instance
+        *puzzle = D::default();
+        let configured = header.clone().and_then(|(mut header, _)| {
+            header.merge(params);
+            puzzle.configure(&header)
+        });
+        if let Err(error) = configured {
+            writeln!(err, " - Invalid parameters: {}", error)?;
+            analyzer.after_day(day);
+            return Ok(answers);
+        }
+
+        analyzer.before_parse(day, &info);
+        let parsed = catch_panic(|| puzzle.parse(input))
+            .unwrap_or_else(|message| Err(ParseError::panicked(message)))
+            .map_err(|error| error.with_day(day));
+        analyzer.after_parse(day, parsed.as_ref().map(|_| ()));
+        if let Err(error) = parsed {
+            writeln!(err, " - Parse error: {}", error)?;
+            analyzer.after_day(day);
+            return Ok(answers);
+        }
+
+        for part in parts.iter().cloned() {
+            analyzer.before_part(day, part);
+            let result = match analyzer.timeout() {
+                Some(timeout) => solve_with_timeout(puzzle, part, timeout),
+                None => solve(puzzle, part),
+            };
+            let outcome = match &result {
+                Ok(answer) => Outcome::Solved(answer),
+                Err(error) => Outcome::Failed(error),
+            };
+            analyzer.after_part(day, part, outcome);
+            let timed_out = matches!(result, Err(SolveError::TimedOut(_)));
+            if sample == 0 {
+                report_part(
+                    &*puzzle,
+                    part,
+                    result,
+                    &mut answers,
+                    analyzer.verbose(),
+                    out,
+                    err,
+                )?;
+            }
+            if timed_out {
+                // the parsed puzzle is left behind on the timed out thread
+                analyzer.after_day(day);
+                return Ok(answers);
+            }
+        }
+    }
+    analyzer.after_day(day);
+    Ok(answers)
+}
+
+/// Solves `part` of `puzzle`, catching panics
+pub(crate) fn solve<D: Day>(puzzle: &mut D, part: usize) -> Result<Answer, SolveError> {
+    // converting into an answer is negligible compared to solving the part
+    catch_panic(|| match part {
+        1 => puzzle.part1().map(Into::into),
+        _ => puzzle.part2().map(Into::into),
+    })
+    .unwrap_or_else(|message| Err(SolveError::Panicked(message)))
+}
+
+/// Solves `part` of `puzzle` on a separate thread, giving up after `timeout`
+///
+/// The puzzle is moved to the thread and back. It is left in its default state if the part timed
+/// out, while the thread keeps running in the background.
+pub(crate) fn solve_with_timeout<D: Day + Send + 'static>(
+    puzzle: &mut D,
+    part: usize,
+    timeout: Duration,
+) -> Result<Answer, SolveError> {
+    let mut owned = std::mem::take(puzzle);
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let result = solve(&mut owned, part);
+        // the receiver is gone if the part timed out
+        let _ = tx.send((owned, result));
+    });
+    match rx.recv_timeout(timeout) {
+        Ok((owned, result)) => {
+            *puzzle = owned;
+            result
+        }
+        Err(_) => Err(SolveError::TimedOut(timeout)),
+    }
+}
+
+thread_local! {
+    /// Whether the current thread is inside [catch_panic]
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f`, returning the message of its panic if it panics
+///
+/// The panic is reported by the caller only. Instead of swapping the process-wide panic hook for
+/// each call, which races between days run in parallel, a hook is installed once that stays quiet
+/// on threads inside this function and passes all other panics on to the previous hook.
+pub(crate) fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    static QUIET_HOOK: Once = Once::new();
+    QUIET_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !CATCHING.with(Cell::get) {
+                previous(info);
+            }
+        }));
+    });
+
+    let catching = CATCHING.with(|it| it.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.with(|it| it.set(catching));
+    result.map_err(panic_message)
+}
+
+/// Returns the message a caught panic was raised with
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown panic".to_string(),
+        },
+    }
+}
+
+/// Prints the `result` of a part and passes its answer on to `answers`
+///
+/// Only called for the first sample, as answers and failures of repeated samples are the same.
+fn report_part<D: Day>(
+    puzzle: &D,
+    part: usize,
+    result: Result<Answer, SolveError>,
+    answers: &mut Answers,
+    verbose: bool,
+    out: &mut dyn Write,
+    err: &mut dyn Write,
+) -> io::Result<()> {
+    match result {
+        Ok(answer) => {
+            if verbose {
+                match part {
+                    1 => puzzle.print_part1(out, &answer)?,
+                    _ => puzzle.print_part2(out, &answer)?,
+                }
+            }
+            answers.set(part, answer);
+        }
+        Err(error) => writeln!(err, " - Part {} failed: {}", part, error)?,
+    }
+    Ok(())
+}
+
+/// Runs a single day with its own analyzer, printing to the given outputs
+type RunDay<'a, A> =
+    dyn FnOnce(&mut A, &mut dyn Write, &mut dyn Write) -> io::Result<Answers> + Send + 'a;
+
+/// A day to be run by [run_parallel]
+pub struct Job<'a, A> {
+    day: usize,
+    run: Box<RunDay<'a, A>>,
+}
+
+impl<'a, A> Job<'a, A> {
+    pub fn new(
+        day: usize,
+        run: impl FnOnce(&mut A, &mut dyn Write, &mut dyn Write) -> io::Result<Answers> + Send + 'a,
+    ) -> Self {
+        Self {
+            day,
+            run: Box::new(run),
+        }
+    }
+}
+
+/// Runs all `jobs` concurrently, each with an analyzer forked from `analyzer`
+///
+/// The output of each day is buffered and printed in the order of `jobs` once all days are done,
+/// afterwards the per-day analyzers are merged back into `analyzer`. Returns the answers of each
+/// day along with its number.
+pub fn run_parallel<A: ParallelAnalyzer>(
+    jobs: Vec<Job<'_, A>>,
+    analyzer: &mut A,
+) -> Vec<(usize, Answers)> {
+    let jobs = jobs
+        .into_iter()
+        .map(|job| (job, analyzer.fork()))
+        .collect::<Vec<_>>();
+    let results = jobs
+        .into_par_iter()
+        .map(|(job, mut analyzer)| {
+            let (mut out, mut err) = (Vec::new(), Vec::new());
+            let answers =
+                (job.run)(&mut analyzer, &mut out, &mut err).expect("Could not write to buffer");
+            (job.day, answers, out, err, analyzer)
+        })
+        .collect::<Vec<_>>();
+
+    results
+        .into_iter()
+        .map(|(day, answers, out, err, forked)| {
+            io::stdout()
+                .write_all(&out)
+                .expect("Could not write output");
+            io::stderr()
+                .write_all(&err)
+                .expect("Could not write output");
+            analyzer.merge(forked);
+            (day, answers)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ParseSlice;
+
+    #[derive(Default)]
+    struct Double(usize);
+
+    impl Day for Double {
+        type Result1 = usize;
+        type Result2 = usize;
+
+        fn parse(&mut self, input: &str) -> Result<(), crate::ParseError> {
+            self.0 = input.parse_at(input)?;
+            Ok(())
+        }
+
+        fn part1(&mut self) -> Result<Self::Result1, SolveError> {
+            Ok(self.0 * 2)
+        }
+
+        fn part2(&mut self) -> Result<Self::Result2, SolveError> {
+            Err(SolveError::NoSolution)
+        }
+    }
+
+    #[derive(Default)]
+    struct Faulty;
+
+    impl Day for Faulty {
+        type Result1 = usize;
+        type Result2 = usize;
+
+        fn part1(&mut self) -> Result<Self::Result1, SolveError> {
+            panic!("Bad op")
+        }
+
+        fn part2(&mut self) -> Result<Self::Result2, SolveError> {
+            thread::sleep(Duration::from_secs(1));
+            Ok(0)
+        }
+    }
+
+    /// Abandons parts running longer than the given time
+    struct Timeout(Duration);
+
+    impl Analyzer for Timeout {
+        fn timeout(&self) -> Option<Duration> {
+            Some(self.0)
+        }
+    }
+
+    #[derive(Default)]
+    struct Solved(Vec<(usize, usize)>);
+
+    impl Analyzer for Solved {
+        fn after_part(&mut self, day: usize, part: usize, outcome: Outcome) {
+            if let Outcome::Solved(_) = outcome {
+                self.0.push((day, part));
+            }
+        }
+    }
+
+    impl ParallelAnalyzer for Solved {
+        fn fork(&self) -> Self {
+            Self::default()
+        }
+
+        fn merge(&mut self, other: Self) {
+            self.0.extend(other.0);
+        }
+    }
+
+    #[test]
+    fn run_puzzle() {
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        let answers = super::run_puzzle(
+            &mut Double::default(),
+            3,
+            Some("Doubling"),
+            None,
+            &Params::default(),
+            "21",
+            &mut Solved::default(),
+            &mut out,
+            &mut err,
+        )
+        .unwrap();
+        assert_eq!(answers.get(1), Some(&Answer::Int(42)));
+        assert_eq!(answers.get(2), None);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Day 3: Doubling\n - Part 1: 42\n"
+        );
+        assert_eq!(
+            String::from_utf8(err).unwrap(),
+            " - Part 2 failed: No solution found\n"
+        );
+
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        let answers = super::run_puzzle(
+            &mut Double::default(),
+            3,
+            None,
+            None,
+            &Params::default(),
+            "#!aoc factor=3\n21",
+            &mut Solved::default(),
+            &mut out,
+            &mut err,
+        )
+        .unwrap();
+        assert_eq!(answers, Answers::default());
+        assert_eq!(
+            String::from_utf8(err).unwrap(),
+            " - Invalid parameters: Unknown parameter: factor. The puzzle has no parameters\n"
+        );
+    }
+
+    #[test]
+    fn run_puzzle_isolated() {
+        let run = |part, mut analyzer: Box<dyn Analyzer>| {
+            let (mut out, mut err) = (Vec::new(), Vec::new());
+            let answers = super::run_puzzle(
+                &mut Faulty,
+                5,
+                None,
+                Some(part),
+                &Params::default(),
+                "",
+                &mut analyzer,
+                &mut out,
+                &mut err,
+            )
+            .unwrap();
+            assert_eq!(answers, Answers::default());
+            String::from_utf8(err).unwrap()
+        };
+        assert_eq!(
+            run(1, Box::<Solved>::default()),
+            " - Part 1 failed: Panicked: Bad op\n"
+        );
+        assert_eq!(
+            run(2, Box::new(Timeout(Duration::from_millis(20)))),
+            " - Part 2 failed: Timed out after 20ms\n"
+        );
+    }
+
+    #[test]
+    fn run_parallel() {
+        let mut puzzles = [Double::default(), Double::default(), Double::default()];
+        let jobs = puzzles
+            .iter_mut()
+            .enumerate()
+            .map(|(idx, puzzle)| {
+                Job::new(idx + 1, move |analyzer: &mut Solved, out, err| {
+                    let params = Params::default();
+                    super::run_puzzle(
+                        puzzle,
+                        idx + 1,
+                        None,
+                        Some(1),
+                        &params,
+                        "1",
+                        analyzer,
+                        out,
+                        err,
+                    )
+                })
+            })
+            .collect();
+        let mut analyzer = Solved::default();
+        let days = super::run_parallel(jobs, &mut analyzer)
+            .into_iter()
+            .map(|(day, answers)| (day, answers.get(1).cloned()))
+            .collect::<Vec<_>>();
+        let expected = (1..=3).map(|day| (day, Some(Answer::Int(2))));
+        assert_eq!(days, expected.collect::<Vec<_>>());
+        assert_eq!(analyzer.0, vec![(1, 1), (2, 1), (3, 1)]);
+    }
+}
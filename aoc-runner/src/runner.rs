@@ -0,0 +1,114 @@
+//! Common interface of a year's puzzles, implemented by `#[derive(AoC)]`
+
+use crate::{verify::verify_day, Analyzer, Answers, DayVisitor, ParallelAnalyzer, Params};
+
+/// The registered puzzles of a year
+///
+/// The `inputs` and `answers` of the `run_*` methods hold one entry per day, ordered like
+/// [Runner::days].
+pub trait Runner {
+    /// Return the year of the puzzles, if known
+    fn year(&self) -> Option<usize>;
+
+    /// Return the numbers of all available days, in order
+    fn days(&self) -> &'static [usize];
+
+    /// Return the title of a given day, if known
+    fn title(&self, day: usize) -> Option<&'static str>;
+
+    /// Return the number of available days
+    fn len(&self) -> usize {
+        self.days().len()
+    }
+
+    /// Return true if the container hosts no days
+    fn is_empty(&self) -> bool {
+        self.days().is_empty()
+    }
+
+    /// Runs one or both parts of a given day, returning their answers
+    ///
+    /// Parses and runs the parts [Analyzer::repeat] times, each time on a fresh `Default`
+    /// instance configured by the input's header and `params`, see [Day::configure][crate::Day::configure]. Skips the
+    /// day's parts if its parameters are invalid or its input cannot be parsed.
+    fn run_part(
+        &mut self,
+        day: usize,
+        part: Option<usize>,
+        params: &Params,
+        input: &str,
+        analyzer: &mut impl Analyzer,
+    ) -> Answers;
+
+    /// Runs both parts of a given day, returning their answers
+    fn run_day(&mut self, day: usize, input: &str, analyzer: &mut impl Analyzer) -> Answers {
+        self.run_part(day, None, &Params::default(), input, analyzer)
+    }
+
+    /// Runs both parts of all available days, verifying them against the given known answers
+    ///
+    /// Returns `false` if any answer did not match its known answer.
+    fn run_all<I: AsRef<str>>(
+        &mut self,
+        inputs: &[I],
+        answers: &[Option<Answers>],
+        analyzer: &mut impl Analyzer,
+    ) -> bool {
+        assert_eq!(inputs.len(), self.len());
+        assert_eq!(answers.len(), self.len());
+
+        let mut passed = true;
+
+        analyzer.before_all();
+        for (idx, day) in self.days().iter().cloned().enumerate() {
+            let result = self.run_day(day, inputs[idx].as_ref(), analyzer);
+            passed &= verify_day(day, None, &result, answers[idx].as_ref(), analyzer);
+        }
+        analyzer.after_all();
+        passed
+    }
+
+    /// Runs one or both parts for all days where an input is given, verifying them against the
+    /// given known answers
+    ///
+    /// Returns `false` if any answer did not match its known answer.
+    fn run_some<I: AsRef<str>>(
+        &mut self,
+        part: Option<usize>,
+        inputs: &[Option<I>],
+        answers: &[Option<Answers>],
+        analyzer: &mut impl Analyzer,
+    ) -> bool {
+        assert_eq!(inputs.len(), self.len());
+        assert_eq!(answers.len(), self.len());
+
+        let mut passed = true;
+
+        analyzer.before_all();
+        for (idx, day) in self.days().iter().cloned().enumerate() {
+            if let Some(input) = &inputs[idx] {
+                let result = self.run_part(day, part, &Params::default(), input.as_ref(), analyzer);
+                passed &= verify_day(day, part, &result, answers[idx].as_ref(), analyzer);
+            }
+        }
+        analyzer.after_all();
+        passed
+    }
+
+    /// Runs one or both parts for all days where an input is given in parallel, verifying them
+    /// against the given known answers
+    ///
+    /// Each day runs with its own analyzer forked from `analyzer`, its output is printed in order
+    /// of the days once all days are done. Returns `false` if any answer did not match its known
+    /// answer.
+    fn run_some_parallel<I: AsRef<str> + Sync, A: ParallelAnalyzer>(
+        &mut self,
+        part: Option<usize>,
+        inputs: &[Option<I>],
+        answers: &[Option<Answers>],
+        analyzer: &mut A,
+    ) -> bool;
+
+    /// Calls the visitor for each available day, in order
+    fn visit(&mut self, visitor: &mut impl DayVisitor);
+}
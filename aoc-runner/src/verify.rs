@@ -0,0 +1,125 @@
+//! Verification of puzzle answers against known answers
+
+use std::fmt::Display;
+
+use crate::{Analyzer, Answer};
+
+/// Outcome of verifying a part's answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Verdict {
+    /// The answer matches the known answer
+    Pass,
+    /// The answer differs from the known answer or the part failed
+    Fail,
+    /// There is no known answer to compare against
+    Unknown,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            Verdict::Pass => "PASS",
+            Verdict::Fail => "FAIL",
+            Verdict::Unknown => "UNKNOWN",
+        })
+    }
+}
+
+/// The answers of both parts of a day's puzzle
+///
+/// Used for both the answers produced by a run and the known answers read from an answers file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers([Option<Answer>; 2]);
+
+impl Answers {
+    /// Parses an answers file
+    ///
+    /// The first line holds the answer of part 1, the second line the answer of part 2. Missing or
+    /// empty lines denote unknown answers.
+    pub fn parse(s: &str) -> Self {
+        let mut lines = s.lines().map(str::trim);
+        let mut next = || lines.next().filter(|it| !it.is_empty()).map(Answer::from);
+        Self([next(), next()])
+    }
+
+    /// Returns the answer of `part`, if known
+    pub fn get(&self, part: usize) -> Option<&Answer> {
+        self.0.get(part.checked_sub(1)?)?.as_ref()
+    }
+
+    /// Sets the answer of `part`
+    pub fn set(&mut self, part: usize, answer: impl Into<Answer>) {
+        self.0[part - 1] = Some(answer.into());
+    }
+
+    /// Verifies `answer` against the known answer of `part`
+    ///
+    /// A missing `answer` (i.e. the part failed) is a [Verdict::Fail] if the answer is known.
+    pub fn verify(&self, part: usize, answer: Option<&Answer>) -> Verdict {
+        match (self.get(part), answer) {
+            (None, _) => Verdict::Unknown,
+            (Some(expected), Some(answer)) if expected == answer => Verdict::Pass,
+            (Some(_), _) => Verdict::Fail,
+        }
+    }
+}
+
+/// Verifies the `answers` of `day` against its `expected` answers
+///
+/// Only verifies the given `part` if any, as the other part was not run. Every verdict is passed
+/// on to `analyzer`. Returns `false` if any answer did not match.
+pub fn verify_day(
+    day: usize,
+    part: Option<usize>,
+    answers: &Answers,
+    expected: Option<&Answers>,
+    analyzer: &mut impl Analyzer,
+) -> bool {
+    let mut passed = true;
+    for part in [1, 2]
+        .into_iter()
+        .filter(|it| part.is_none_or(|part| part == *it))
+    {
+        let answer = answers.get(part);
+        let verdict = expected.map_or(Verdict::Unknown, |it| it.verify(part, answer));
+        if verdict == Verdict::Fail {
+            passed = false;
+            eprintln!(
+                "Day {} part {}: expected {}, found {}",
+                day,
+                part,
+                expected
+                    .and_then(|it| it.get(part))
+                    .map(Answer::to_string)
+                    .unwrap_or_default(),
+                answer.map_or_else(|| "no answer".to_string(), Answer::to_string)
+            );
+        }
+        analyzer.part_verified(day, part, verdict);
+    }
+    passed
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn verify() {
+        let expected = Answers::parse("42\n\n");
+        assert_eq!(expected.verify(1, Some(&42.into())), Verdict::Pass);
+        assert_eq!(expected.verify(1, Some(&43.into())), Verdict::Fail);
+        assert_eq!(expected.verify(1, None), Verdict::Fail);
+        assert_eq!(expected.verify(2, Some(&42.into())), Verdict::Unknown);
+
+        let expected = Answers::parse("4,6,3\nco,de\n");
+        assert_eq!(
+            expected.verify(1, Some(&vec![4, 6, 3].into())),
+            Verdict::Pass
+        );
+        assert_eq!(
+            expected.verify(2, Some(&vec!["co", "de"].into())),
+            Verdict::Pass
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use aoc2024::{
+    common::{
+        dimensional_map::{Dim2VecMap, DimensionalHashMap, DimensionalMap},
+        grid::Grid,
+    },
+    days::Days,
+};
+use aoc_runner::{inputs, Runner};
+use criterion::{black_box, criterion_group, Criterion};
+
+/// Days whose input is a single map of characters
+const GRID_DAYS: [usize; 7] = [4, 6, 8, 10, 12, 16, 20];
+
+const NEIGHBOURS: [[i16; 2]; 4] = [[-1, 0], [0, 1], [1, 0], [0, -1]];
+
+fn entries(input: &str) -> impl Iterator<Item = ([i16; 2], char)> + '_ {
+    input.lines().enumerate().flat_map(|(y, line)| {
+        line.chars()
+            .enumerate()
+            .map(move |(x, ch)| ([y as i16, x as i16], ch))
+    })
+}
+
+/// Sums up the neighbours of every entry, as the grid days look up the neighbours of their cells
+fn sum_neighbours<M: DimensionalMap<i16, char>>(map: &M) -> u32 {
+    map.iter()
+        .flat_map(|([y, x], _)| NEIGHBOURS.iter().map(move |[dy, dx]| [y + dy, x + dx]))
+        .filter_map(|k| map.get(&k))
+        .map(|ch| *ch as u32)
+        .sum()
+}
+
+fn sum_grid_neighbours(grid: &Grid<char>) -> u32 {
+    grid.coords()
+        .flat_map(|coord| grid.neighbours(coord))
+        .map(|coord| grid[coord] as u32)
+        .sum()
+}
+
+/// Compares building and looking up the maps of the grid days with each backend and [Grid]
+fn bench(c: &mut Criterion) {
+    let year = Days::new().year();
+    for day in GRID_DAYS {
+        let input_file_path = inputs::input_path(year, day);
+        let Ok(input) = std::fs::read_to_string(&input_file_path) else {
+            eprintln!(
+                "Skipping day {}: {} not found",
+                day,
+                input_file_path.display()
+            );
+            continue;
+        };
+
+        let mut group = c.benchmark_group(format!("day {:0>2} - map", day));
+        group.bench_function("grid build", |b| {
+            b.iter(|| Grid::try_from(black_box(input.as_str())))
+        });
+        group.bench_function("hash build", |b| {
+            b.iter(|| DimensionalHashMap::<i16, char>::from_iter(entries(black_box(&input))))
+        });
+        group.bench_function("vec build", |b| {
+            b.iter(|| Dim2VecMap::<i16, char>::from_iter(entries(black_box(&input))))
+        });
+
+        let Ok(grid) = Grid::try_from(input.as_str()) else {
+            eprintln!("Skipping lookups of day {}: not a grid", day);
+            continue;
+        };
+        let hash_map = DimensionalHashMap::<i16, char>::from_iter(entries(&input));
+        let vec_map = Dim2VecMap::<i16, char>::from_iter(entries(&input));
+        group.bench_function("grid lookup", |b| {
+            b.iter(|| sum_grid_neighbours(black_box(&grid)))
+        });
+        group.bench_function("hash lookup", |b| {
+            b.iter(|| sum_neighbours(black_box(&hash_map)))
+        });
+        group.bench_function("vec lookup", |b| {
+            b.iter(|| sum_neighbours(black_box(&vec_map)))
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench);
+criterion::criterion_main!(benches);
@@ -9,13 +9,46 @@
 //!
 //! #[derive(Analyzer)]
 //! #[derive(AoC)]
+//! #[year(2024)]
+//! pub(crate) struct Days {
+//!   #[day(1, title = "Historian Hysteria")]
+//!   day01: day01::Day01,
+//!   #[day(2, title = "Red-Nosed Reports")]
+//!   day02: day02::Day02,
+//!   // days may have gaps
+//!   #[day(7, title = "Bridge Repair")]
+//!   day07: day07::Day07,
+//! }
+//! ```
+//!
+//! ## Using positional days
+//! Fields without a `#[day(..)]` attribute are numbered by their position.
+//! ```ignore
+//! use aoc_runner::{Day,Analyzer};
+//! use derive_aoc_runner::{AoC, Analyzer};
+//!
+//! #[derive(Analyzer)]
+//! #[derive(AoC)]
 //! pub(crate) struct Days(
 //!   day01::Day01,
 //!   day02::Day02,
 //!   day03::Day03,
-//!   day04::Day04,
-//!   day05::Day05,
-//!   day06::Day06,
+//! );
+//! ```
+//!
+//! ## Choosing analyzers
+//! Several analyzers receive the same hooks when listed in an `#[analyzer(..)]` attribute. Each
+//! prints its own report, the memory report following the timings here.
+//! ```ignore
+//! use aoc_runner::{Day,Analyzer,MemoryAnalyzer};
+//! use derive_aoc_runner::{AoC, Analyzer};
+//!
+//! #[derive(Analyzer)]
+//! #[analyzer(MemoryAnalyzer, TimeAnalyzer)]
+//! #[derive(AoC)]
+//! pub(crate) struct Days(
+//!   day01::Day01,
+//!   day02::Day02,
 //! );
 //! ```
 //!
@@ -47,214 +80,334 @@
 //!
 //! ## Run puzzles
 //! ```rust
+//! use aoc_runner::{Params, Runner};
+//!
 //! let runner = Days::new(); // creates new runner with Default::default() values for puzzles
-//! runner.run_day( 1 /* day */,               "" /* input */, &mut runner.get_analyzer());
-//! runner.run_part(1 /* day */, 2 /* part */, "" /* input */, &mut runner.get_analyzer());
-//! runner.run_all(["", ""] /* inputs */,                      &mut runner.get_analyzer());
+//! runner.run_day( 1 /* day */,                     "" /* input */, &mut runner.get_analyzer());
+//! runner.run_part(1 /* day */, Some(2) /* part */, &Params::default() /* parameters */, "" /* input */, &mut runner.get_analyzer());
+//! runner.run_all(&["", ""] /* inputs */, &[None, None] /* known answers */, &mut runner.get_analyzer());
+//! ```
+//!
+//! ## Dispatch to several years
+//! All derived structs implement [Runner][aoc_runner::Runner], so one binary can run several years:
+//! ```ignore
+//! fn run(runner: impl Runner) { /* ... */ }
+//!
+//! match year {
+//!     2023 => run(aoc2023::Days::new()),
+//!     2024 => run(aoc2024::Days::new()),
+//!     _ => panic!("Invalid year: {}", year),
+//! }
 //! ```
 //!
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, Data, DeriveInput, Fields, FieldsUnnamed};
+use syn::{
+    parse_macro_input, punctuated::Punctuated, Attribute, Data, DeriveInput, Fields, Index, Lit,
+    Member, Meta, MetaNameValue, NestedMeta, Token, Type,
+};
+
+/// A day registered as field of the struct deriving [AoC]
+struct DayField {
+    day: usize,
+    title: Option<String>,
+    member: Member,
+    ty: Type,
+}
+
+/// Parses a `#[day(7)]` or `#[day(7, title = "Bridge Repair")]` attribute into day and title
+fn parse_day_attr(attr: &Attribute) -> (usize, Option<String>) {
+    let usage = "Expected #[day(<number>)] or #[day(<number>, title = \"<title>\")]";
+    let Ok(Meta::List(list)) = attr.parse_meta() else {
+        panic!("{}", usage)
+    };
+    let mut day = None;
+    let mut title = None;
+    for nested in list.nested {
+        match nested {
+            NestedMeta::Lit(Lit::Int(lit)) => day = Some(lit.base10_parse().expect(usage)),
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                path,
+                lit: Lit::Str(lit),
+                ..
+            })) if path.is_ident("title") => title = Some(lit.value()),
+            _ => panic!("{}", usage),
+        }
+    }
+    (day.expect(usage), title)
+}
+
+/// Parses a `#[year(2024)]` attribute
+fn parse_year_attr(attr: &Attribute) -> usize {
+    let usage = "Expected #[year(<number>)]";
+    let Ok(Meta::List(list)) = attr.parse_meta() else {
+        panic!("{}", usage)
+    };
+    match list.nested.first() {
+        Some(NestedMeta::Lit(Lit::Int(lit))) if list.nested.len() == 1 => {
+            lit.base10_parse().expect(usage)
+        }
+        _ => panic!("{}", usage),
+    }
+}
+
+/// Collects the days registered as `fields`, ordered by day
+///
+/// Fields without a `#[day(..)]` attribute are registered by their position, starting with day 1.
+fn parse_day_fields(fields: Fields) -> Vec<DayField> {
+    let mut days = fields
+        .into_iter()
+        .enumerate()
+        .map(|(idx, field)| {
+            let (day, title) = field
+                .attrs
+                .iter()
+                .find(|attr| attr.path.is_ident("day"))
+                .map_or((idx + 1, None), parse_day_attr);
+            let member = match field.ident {
+                Some(ident) => Member::Named(ident),
+                None => Member::Unnamed(Index::from(idx)),
+            };
+            DayField {
+                day,
+                title,
+                member,
+                ty: field.ty,
+            }
+        })
+        .collect::<Vec<_>>();
+    days.sort_by_key(|it| it.day);
+    for pair in days.windows(2) {
+        if pair[0].day == pair[1].day {
+            panic!("Day {} is registered more than once", pair[0].day);
+        }
+    }
+    if days.first().is_some_and(|it| it.day == 0) {
+        panic!("Days start at 1");
+    }
+    days
+}
 
 /// Derives multiple utility methods for running Advent of Code puzzles on a struct
 ///
+/// Each field holds a day's puzzle. Days are numbered by a `#[day(7)]` or
+/// `#[day(7, title = "Bridge Repair")]` attribute on their field, or by their position if the
+/// attribute is missing. Days may have gaps and are always run in order.
+///
+/// The year of the puzzles is given by a `#[year(2024)]` attribute on the struct.
+///
 /// Derives
 /// - `fn new() -> Self`
-/// - `fn len(&self) -> usize`
-/// - `fn is_empty(&self) -> bool`
-/// - `fn run_day(&self, day: usize, input: &str, analyzer: &mut impl Analyzer)`
-/// - `fn run_part(&self, day: usize, part: usize, input: &str, analyzer: &mut impl Analyzer)`
-/// - `fn run_all<I: AsRef<str>>(&mut self, inputs: &[I])`
-#[proc_macro_derive(AoC)]
+/// - an implementation of [Runner][aoc_runner::Runner], providing
+///   - `fn year(&self) -> Option<usize>`
+///   - `fn days(&self) -> &'static [usize]`
+///   - `fn title(&self, day: usize) -> Option<&'static str>`
+///   - `fn run_part(&mut self, day: usize, part: Option<usize>, params: &Params, input: &str, analyzer: &mut impl Analyzer) -> Answers`
+///   - `fn run_some_parallel<I: AsRef<str> + Sync, A: ParallelAnalyzer>(&mut self, part: Option<usize>, inputs: &[Option<I>], answers: &[Option<Answers>], analyzer: &mut A) -> bool`
+///   - `fn visit(&mut self, visitor: &mut impl DayVisitor)`
+#[proc_macro_derive(AoC, attributes(day, year))]
 pub fn derive_aoc(input: TokenStream) -> TokenStream {
-    let DeriveInput { ident, data, .. } = parse_macro_input!(input);
+    let DeriveInput {
+        ident, data, attrs, ..
+    } = parse_macro_input!(input);
 
-    let usage = format!(
-        "#[derive(AoC)] can currently only be applied to structs with unnamend fields.
+    let usage = "#[derive(AoC)] can currently only be applied to structs with fields.
 
 Also, alls fields must implement aoc_runner::Day + Default.
 
 Example:
 #[derive(Aoc)]
-struct Aoc2022(
-    day01::Day01,
-    day02::Day02,
-);"
-    );
-
-    if let Data::Struct(s) = data {
-        if let Fields::Unnamed(FieldsUnnamed { unnamed, .. }) = s.fields {
-            let fields_len = unnamed.len();
-            let fields_ty = unnamed.iter().map(|f| &f.ty);
-            let field_indices = (0..fields_len).map(syn::Index::from);
+#[year(2022)]
+struct Aoc2022 {
+    #[day(1, title = \"Calorie Counting\")]
+    day01: day01::Day01,
+    #[day(2, title = \"Rock Paper Scissors\")]
+    day02: day02::Day02,
+}";
 
-            let new_impl = {
-                let doc = format!(
-                    "Creates a new [{}], initialized with default values for all days",
-                    ident
-                );
-                quote! {
-                    #[doc = #doc]
-                    pub fn new() -> Self {
-                        Self(#(<#fields_ty>::default(),)*)
-                    }
-                }
-            };
+    let Data::Struct(s) = data else {
+        panic!("{}", usage)
+    };
+    if matches!(s.fields, Fields::Unit) {
+        panic!("{}", usage)
+    }
 
-            let len_impl = {
-                let doc = "Return the number of available days";
-                quote! {
-                    #[doc = #doc]
-                    pub fn len(&self) -> usize {
-                        #fields_len
-                    }
-                }
-            };
+    let year = attrs
+        .iter()
+        .find(|attr| attr.path.is_ident("year"))
+        .map(parse_year_attr);
+    let fields = parse_day_fields(s.fields);
+    let days_len = fields.len();
+    let days = fields.iter().map(|it| it.day).collect::<Vec<_>>();
+    let members = fields.iter().map(|it| &it.member).collect::<Vec<_>>();
+    let tys = fields.iter().map(|it| &it.ty);
+    let titles = fields
+        .iter()
+        .map(|it| match &it.title {
+            Some(title) => quote! { Some(#title) },
+            None => quote! { None },
+        })
+        .collect::<Vec<_>>();
+    let positions = 0..days_len;
+    let valid_days = days
+        .iter()
+        .map(|day| day.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
 
-            let is_empty_impl = {
-                let doc = "Return true if the container hosts no days";
-                quote! {
-                    #[doc = #doc]
-                    pub fn is_empty(&self) -> bool {
-                        #fields_len == 0
-                    }
+    let new_impl = {
+        let doc = format!(
+            "Creates a new [{}], initialized with default values for all days",
+            ident
+        );
+        quote! {
+            #[doc = #doc]
+            pub fn new() -> Self {
+                Self {
+                    #( #members: <#tys>::default(), )*
                 }
-            };
+            }
+        }
+    };
 
-            let run_day_impl = {
-                let doc = "Runs both parts of a given day";
-                quote! {
-                    #[doc = #doc]
-                    pub fn run_day(&mut self, day: usize, input: &str, analyzer: &mut impl Analyzer) {
-                        self.run_part(day, None, input, analyzer);
-                    }
-                }
-            };
+    let year_impl = {
+        let year = match year {
+            Some(year) => quote! { Some(#year) },
+            None => quote! { None },
+        };
+        quote! {
+            fn year(&self) -> Option<usize> {
+                #year
+            }
+        }
+    };
 
-            let run_part_impl = {
-                let doc = "Runs one or both parts of a given day";
-                quote! {
-                    #[doc = #doc]
-                    pub fn run_part(&mut self, day: usize, part: Option<usize>, input: &str, mut analyzer: &mut impl Analyzer) {
-                        match day - 1 {
-                            #( #field_indices => {
-                                println!("Day {}", day);
+    let days_impl = quote! {
+        fn days(&self) -> &'static [usize] {
+            &[#( #days ),*]
+        }
+    };
 
-                                analyzer.before_day(day);
-                                analyzer.before_parse(day);
-                                self.#field_indices.parse(input);
-                                analyzer.after_parse(day);
-                                match part {
-                                    Some(1) => {
-                                        analyzer.before_part(day, 1);
-                                        let result = self.#field_indices.part1();
-                                        analyzer.after_part(day, 1);
-                                        self.#field_indices.print_part1(result);
-                                    }
-                                    Some(2) => {
-                                        analyzer.before_part(day, 2);
-                                        let result = self.#field_indices.part2();
-                                        analyzer.after_part(day, 2);
-                                        self.#field_indices.print_part2(result);
-                                    }
-                                    None => {
-                                        analyzer.before_part(day, 1);
-                                        let result = self.#field_indices.part1();
-                                        analyzer.after_part(day, 1);
-                                        self.#field_indices.print_part1(result);
+    let title_impl = quote! {
+        fn title(&self, day: usize) -> Option<&'static str> {
+            match day {
+                #( #days => #titles, )*
+                _ => None,
+            }
+        }
+    };
 
-                                        analyzer.before_part(day, 2);
-                                        let result = self.#field_indices.part2();
-                                        analyzer.after_part(day, 2);
-                                        self.#field_indices.print_part2(result);
-                                    }
-                                    Some(part) => panic!("Invalid part: {}. Valid parts are: 1,2", part)
-                                }
-                                analyzer.after_day(day);
-                            } )*
-                            _ => panic!("Invalid day: {}. Valid days are 1..{}", day, #fields_len)
-                        }
-                    }
+    let run_part_impl = quote! {
+        fn run_part(&mut self, day: usize, part: Option<usize>, params: &::aoc_runner::Params, input: &str, analyzer: &mut impl ::aoc_runner::Analyzer) -> ::aoc_runner::Answers {
+            let (out, err) = (&mut ::std::io::stdout(), &mut ::std::io::stderr());
+            let answers = match day {
+                #( #days => ::aoc_runner::run::run_puzzle(&mut self.#members, day, #titles, part, params, input, analyzer, out, err), )*
+                _ => {
+                    use ::std::io::Write;
+                    writeln!(err, "Invalid day: {}. Valid days are: {}", day, #valid_days)
+                        .map(|_| ::aoc_runner::Answers::default())
                 }
             };
+            answers.expect("Could not write output")
+        }
+    };
 
-            let run_all_impl = {
-                let doc = "Runs both parts of all available days";
-                quote! {
-                    #[doc = #doc]
-                    pub fn run_all<I: AsRef<str>>(&mut self, inputs: &[I]) {
-                        assert_eq!(inputs.len(), self.len());
+    let run_some_parallel_impl = {
+        let positions = positions.clone();
+        quote! {
+            fn run_some_parallel<I: AsRef<str> + Sync, A: ::aoc_runner::ParallelAnalyzer>(&mut self, part: Option<usize>, inputs: &[Option<I>], answers: &[Option<::aoc_runner::Answers>], analyzer: &mut A) -> bool {
+                assert_eq!(inputs.len(), self.len());
+                assert_eq!(answers.len(), self.len());
 
-                        let mut analyzer = self.get_analyzer();
+                let mut passed = true;
+                let mut jobs = Vec::new();
 
-                        analyzer.before_all();
-                        for i in 1..=self.len() {
-                            let input = inputs[i - 1].as_ref();
-                            self.run_day(i, input, &mut analyzer);
-                        }
-                        analyzer.after_all();
+                analyzer.before_all();
+                #(
+                    if let Some(input) = &inputs[#positions] {
+                        let puzzle = &mut self.#members;
+                        jobs.push(::aoc_runner::run::Job::new(#days, move |analyzer: &mut A, out, err| {
+                            let params = ::aoc_runner::Params::default();
+                            ::aoc_runner::run::run_puzzle(puzzle, #days, #titles, part, &params, input.as_ref(), analyzer, out, err)
+                        }));
                     }
+                )*
+                let results = ::aoc_runner::run::run_parallel(jobs, analyzer);
+                for (day, result) in results {
+                    let idx = self.days().iter().position(|it| *it == day).unwrap();
+                    passed &= ::aoc_runner::verify::verify_day(day, part, &result, answers[idx].as_ref(), analyzer);
                 }
-            };
-
-            let run_some_impl = {
-                let doc = "Runs both parts for all days where an input is given";
-                quote! {
-                    #[doc = #doc]
-                    pub fn run_some<I: AsRef<str>>(&mut self, inputs: &[Option<I>]) {
-                        assert_eq!(inputs.len(), self.len());
-
-                        let mut analyzer = self.get_analyzer();
+                analyzer.after_all();
+                passed
+            }
+        }
+    };
 
-                        analyzer.before_all();
-                        for i in 1..=self.len() {
-                            if let Some(input) = &inputs[i - 1] {
-                                self.run_day(i, input.as_ref(), &mut analyzer);
-                            }
-                        }
-                        analyzer.after_all();
-                    }
-                }
-            };
+    let visit_impl = quote! {
+        fn visit(&mut self, visitor: &mut impl ::aoc_runner::DayVisitor) {
+            #( visitor.visit(#days, &mut self.#members); )*
+        }
+    };
 
-            let output = quote! {
-                impl #ident {
-                    #new_impl
-                    #len_impl
-                    #is_empty_impl
-                    #run_day_impl
-                    #run_part_impl
-                    #run_all_impl
-                    #run_some_impl
-                }
-            };
+    let output = quote! {
+        impl #ident {
+            #new_impl
+        }
 
-            // panic!("{}", output.to_string());
-            output.into()
-        } else {
-            panic!("{}", usage)
+        impl ::aoc_runner::Runner for #ident {
+            #year_impl
+            #days_impl
+            #title_impl
+            #run_part_impl
+            #run_some_parallel_impl
+            #visit_impl
         }
-    } else {
-        panic!("{}", usage)
-    }
+    };
+
+    // panic!("{}", output.to_string());
+    output.into()
 }
 
 /// Derives a method for this struct that returns an [Analyzer][aoc_runner::Analyzer] instance
 ///
+/// The analyzer is a [TimeAnalyzer][aoc_runner::TimeAnalyzer] by default. An
+/// `#[analyzer(MemoryAnalyzer, TimeAnalyzer)]` attribute on the struct picks the analyzers instead,
+/// which are created by `Default::default()` and combined into a tuple receiving the same hooks.
+///
 /// Derives
 /// - `fn get_analyzer(&self) -> impl Analyzer`
-#[proc_macro_derive(Analyzer)]
+#[proc_macro_derive(Analyzer, attributes(analyzer))]
 pub fn derive_get_analyzer(input: TokenStream) -> TokenStream {
-    let DeriveInput { ident, .. } = parse_macro_input!(input);
+    let DeriveInput { ident, attrs, .. } = parse_macro_input!(input);
+
+    let usage = "Expected #[analyzer(<type>, ...)] with at least one analyzer type";
+    let analyzers = attrs
+        .iter()
+        .find(|attr| attr.path.is_ident("analyzer"))
+        .map(|attr| {
+            let types = attr
+                .parse_args_with(Punctuated::<Type, Token![,]>::parse_terminated)
+                .expect(usage);
+            if types.is_empty() {
+                panic!("{}", usage);
+            }
+            types.into_iter().collect::<Vec<_>>()
+        });
 
     let get_analyzer_impl = {
         let doc = "Creates a new analyzer to use during execution";
+        let analyzer = match analyzers.as_deref() {
+            None => quote! { TimeAnalyzer::new() },
+            Some([ty]) => quote! { <#ty as ::std::default::Default>::default() },
+            Some(types) => quote! {
+                (#(<#types as ::std::default::Default>::default(),)*)
+            },
+        };
         quote! {
             #[doc = #doc]
             pub fn get_analyzer(&self) -> impl Analyzer {
-                TimeAnalyzer::new()
+                #analyzer
             }
         }
     };
@@ -0,0 +1,275 @@
+//! Command line interface of the runner
+
+use std::{fmt::Display, ops::RangeInclusive, path::PathBuf, str::FromStr, time::Duration};
+
+use aoc_runner::{Format, Params};
+use clap::{error::ErrorKind, Args, CommandFactory, Parser, Subcommand};
+
+/// Runs Advent of Code puzzles
+#[derive(Parser)]
+#[command(version, about, args_conflicts_with_subcommands = true)]
+pub struct Cli {
+    /// Year of the puzzles, defaults to the latest registered year
+    #[arg(short, long, global = true)]
+    pub year: Option<usize>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Arguments of `run`, which is the default command
+    #[command(flatten)]
+    pub run: RunArgs,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Runs puzzles, printing their answers and timings
+    Run(RunArgs),
+    /// Runs puzzles repeatedly, printing timing statistics
+    Bench(BenchArgs),
+    /// Runs puzzles, verifying their answers against the known answers in ./answers or the
+    /// expected answers of their examples
+    Verify(VerifyArgs),
+    /// Lists all registered days
+    List,
+    /// Checks, normalizes and downloads the inputs in ./input
+    Inputs(InputsArgs),
+    /// Generates the module of a new day, registers it and creates an empty input placeholder
+    New {
+        /// Day to scaffold
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Title of the day's puzzle
+        #[arg(short, long)]
+        title: Option<String>,
+    },
+}
+
+/// Selects the days and parts to run
+#[derive(Args)]
+pub struct Selection {
+    /// Day to run, reading its input from stdin unless `--input` is given
+    #[arg(conflicts_with = "days")]
+    pub day: Option<usize>,
+
+    /// Days to run with their inputs read from ./input, e.g. `3..7`, `3..=7` or `1,4,9`
+    #[arg(short, long)]
+    pub days: Option<DayRanges>,
+
+    /// Part to run, runs both parts if missing
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub part: Option<u8>,
+
+    /// Input file of the given day
+    #[arg(short, long, requires = "day")]
+    pub input: Option<PathBuf>,
+
+    /// Parameters of the given day overriding its defaults, e.g. `--param width=11,height=7`
+    #[arg(long = "param", value_name = "NAME=VALUE", requires = "day")]
+    pub params: Vec<Params>,
+}
+
+/// Configures how timings are reported
+#[derive(Args)]
+pub struct ReportArgs {
+    /// Format of the report: table, json or csv
+    #[arg(short, long, default_value = "table")]
+    pub format: Format,
+
+    /// File to store the timings of this run in, compared against on the next run
+    #[arg(short, long)]
+    pub baseline: Option<PathBuf>,
+
+    /// Slowdown in percent against the baseline that is flagged as regression
+    #[arg(short, long, default_value_t = 10.0)]
+    pub threshold: f64,
+
+    /// Reports the allocations and peak memory of each parse and part, slowing down the timings
+    #[arg(short, long)]
+    pub memory: bool,
+}
+
+#[derive(Args)]
+pub struct RunArgs {
+    #[command(flatten)]
+    pub selection: Selection,
+
+    #[command(flatten)]
+    pub report: ReportArgs,
+
+    /// Number of times to parse and run each part, each time on a fresh instance
+    #[arg(short, long, default_value_t = 1)]
+    pub repeat: usize,
+
+    /// Runs all days concurrently
+    #[arg(long)]
+    pub parallel: bool,
+
+    /// Seconds after which a part is abandoned and reported as failed
+    #[arg(long, value_name = "SECONDS", value_parser = parse_seconds)]
+    pub timeout: Option<Duration>,
+}
+
+#[derive(Args)]
+pub struct BenchArgs {
+    #[command(flatten)]
+    pub selection: Selection,
+
+    #[command(flatten)]
+    pub report: ReportArgs,
+
+    /// Number of times to parse and run each part, each time on a fresh instance
+    #[arg(short, long, default_value_t = 10)]
+    pub repeat: usize,
+
+    /// Seconds after which a part is abandoned and reported as failed
+    #[arg(long, value_name = "SECONDS", value_parser = parse_seconds)]
+    pub timeout: Option<Duration>,
+}
+
+#[derive(Args)]
+pub struct VerifyArgs {
+    #[command(flatten)]
+    pub selection: Selection,
+
+    /// Format of the report: table, json or csv
+    #[arg(short, long, default_value = "table")]
+    pub format: Format,
+
+    /// Also fails if an answer is unknown
+    #[arg(long)]
+    pub strict: bool,
+
+    /// Runs the examples in ./examples instead of the inputs
+    #[arg(short, long, conflicts_with = "input")]
+    pub examples: bool,
+
+    /// Seconds after which a part is abandoned and reported as failed
+    #[arg(long, value_name = "SECONDS", value_parser = parse_seconds)]
+    pub timeout: Option<Duration>,
+}
+
+#[derive(Args)]
+pub struct InputsArgs {
+    /// Days to manage the inputs of, e.g. `3..7`, `3..=7` or `1,4,9`
+    #[arg(short, long)]
+    pub days: Option<DayRanges>,
+
+    /// Downloads the inputs that are not cached yet
+    #[arg(long)]
+    pub fetch: bool,
+
+    /// Fixes line endings of inputs in place
+    #[arg(long)]
+    pub normalize: bool,
+
+    /// File holding the session cookie of adventofcode.com, used by `--fetch`
+    #[arg(long, default_value = ".session")]
+    pub session: PathBuf,
+}
+
+/// Exits with an error message formatted like all other command line errors
+pub fn fail(kind: ErrorKind, message: impl Display) -> ! {
+    Cli::command().error(kind, message).exit()
+}
+
+/// Parses a positive number of seconds, e.g. `2.5`
+fn parse_seconds(s: &str) -> Result<Duration, String> {
+    let seconds = s
+        .parse::<f64>()
+        .map_err(|_| format!("Invalid number of seconds: {}", s))?;
+    match Duration::try_from_secs_f64(seconds) {
+        Ok(duration) if !duration.is_zero() => Ok(duration),
+        _ => Err(format!(
+            "Invalid number of seconds: {}. Expected a positive number",
+            s
+        )),
+    }
+}
+
+/// A set of days, given as comma separated days or ranges of days
+///
+/// Ranges follow Rust's syntax, i.e. `3..7` excludes and `3..=7` includes day 7. Either end of a
+/// range may be omitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayRanges(Vec<RangeInclusive<usize>>);
+
+impl DayRanges {
+    pub fn contains(&self, day: usize) -> bool {
+        self.0.iter().any(|range| range.contains(&day))
+    }
+
+    /// Returns the days that were given on their own rather than as part of a range
+    pub fn single_days(&self) -> impl Iterator<Item = usize> + '_ {
+        self.0
+            .iter()
+            .filter(|range| range.start() == range.end())
+            .map(|range| *range.start())
+    }
+}
+
+impl FromStr for DayRanges {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let day = |s: &str, default: usize| match s.trim() {
+            "" => Ok(default),
+            s => match s.parse::<usize>() {
+                Ok(0) => Err("Invalid day: 0. Days start at 1".to_string()),
+                Ok(day) => Ok(day),
+                Err(_) => Err(format!("Invalid day: {}", s)),
+            },
+        };
+        let ranges = s
+            .split(',')
+            .map(|range| {
+                if let Some((start, end)) = range.split_once("..=") {
+                    Ok(day(start, 1)?..=day(end, usize::MAX)?)
+                } else if let Some((start, end)) = range.split_once("..") {
+                    let end = day(end, usize::MAX)?;
+                    Ok(day(start, 1)?..=end.saturating_sub(1))
+                } else if range.trim().is_empty() {
+                    Err(format!("Missing day in list: {}", s))
+                } else {
+                    let day = day(range, 0)?;
+                    Ok(day..=day)
+                }
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Self(ranges))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn day_ranges() {
+        let ranges = "3..7".parse::<DayRanges>().unwrap();
+        assert!(!ranges.contains(2));
+        assert!(ranges.contains(3));
+        assert!(ranges.contains(6));
+        assert!(!ranges.contains(7));
+
+        let ranges = "1,4..=5,20..".parse::<DayRanges>().unwrap();
+        let days = (1..=25).filter(|day| ranges.contains(*day));
+        assert_eq!(
+            days.collect::<Vec<_>>(),
+            vec![1, 4, 5, 20, 21, 22, 23, 24, 25]
+        );
+
+        assert!("3..x".parse::<DayRanges>().is_err());
+        assert!("1,,3".parse::<DayRanges>().is_err());
+        assert!("1,".parse::<DayRanges>().is_err());
+        assert!("0".parse::<DayRanges>().is_err());
+        assert!("0..=3".parse::<DayRanges>().is_err());
+        assert!(Cli::try_parse_from(["aoc2024", "7", "--part", "3"]).is_err());
+    }
+
+    #[test]
+    fn cli() {
+        Cli::command().debug_assert();
+    }
+}
@@ -1,28 +1,56 @@
-use std::{borrow::Borrow, hash::Hash};
+//! Maps of N-dimensional integer keys, backed by a hash map or a dense vector
+
+use std::{borrow::Borrow, hash::Hash, ops::RangeInclusive};
 
 use fxhash::FxHashMap;
+use num::PrimInt;
 
+/// A map of N-dimensional keys that keeps track of the bounds of its keys
 pub trait DimensionalMap<K, V, const N: usize = 2>:
     FromIterator<([K; N], V)> + MinMax<K, N>
 {
     fn insert<U: Into<V>>(&mut self, k: [K; N], v: U);
     fn get<U: Borrow<[K; N]>>(&self, k: &U) -> Option<&V>;
+    fn get_mut<U: Borrow<[K; N]>>(&mut self, k: &U) -> Option<&mut V>;
+
+    /// Number of entries
+    fn len(&self) -> usize;
+
+    /// All entries in no particular order
+    fn iter<'a>(&'a self) -> impl Iterator<Item = ([K; N], &'a V)>
+    where
+        V: 'a;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn contains_key<U: Borrow<[K; N]>>(&self, k: &U) -> bool {
+        self.get(k).is_some()
+    }
 }
 
 //------------------------------------------
 // DimensionalHashMap
 //------------------------------------------
 
-#[derive(Debug, Clone, Default)]
-pub struct DimensionalHashMap<K: Copy, V, const N: usize = 2> {
+/// A [DimensionalMap] storing its entries in a hash map, which suits sparse keys
+#[derive(Debug, Clone)]
+pub struct DimensionalHashMap<K, V, const N: usize = 2> {
     map: FxHashMap<[K; N], V>,
     min_max: MinMaxHolder<K, N>,
 }
 
-impl<K, V, const N: usize> MinMax<K, N> for DimensionalHashMap<K, V, N>
-where
-    K: Copy + Clone + Ord + Default,
-{
+impl<K, V, const N: usize> Default for DimensionalHashMap<K, V, N> {
+    fn default() -> Self {
+        Self {
+            map: Default::default(),
+            min_max: Default::default(),
+        }
+    }
+}
+
+impl<K, V, const N: usize> MinMax<K, N> for DimensionalHashMap<K, V, N> {
     fn min(&self) -> &[Option<K>; N] {
         self.min_max.min()
     }
@@ -34,8 +62,7 @@ where
 
 impl<K, V, const N: usize> DimensionalMap<K, V, N> for DimensionalHashMap<K, V, N>
 where
-    K: Copy + Clone + Ord + Default + Hash,
-    V: Default,
+    K: Copy + Ord + Hash,
 {
     fn insert<U: Into<V>>(&mut self, k: [K; N], v: U) {
         self.min_max.insert(k);
@@ -45,12 +72,26 @@ where
     fn get<U: Borrow<[K; N]>>(&self, k: &U) -> Option<&V> {
         self.map.get(k.borrow())
     }
+
+    fn get_mut<U: Borrow<[K; N]>>(&mut self, k: &U) -> Option<&mut V> {
+        self.map.get_mut(k.borrow())
+    }
+
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = ([K; N], &'a V)>
+    where
+        V: 'a,
+    {
+        self.map.iter().map(|(k, v)| (*k, v))
+    }
 }
 
 impl<K, V, const N: usize> FromIterator<([K; N], V)> for DimensionalHashMap<K, V, N>
 where
-    K: Default + Copy + Clone + Ord + Hash,
-    V: Default,
+    K: Copy + Ord + Hash,
 {
     fn from_iter<I: IntoIterator<Item = ([K; N], V)>>(iter: I) -> Self {
         let mut this = Self::default();
@@ -65,67 +106,150 @@ where
 // DimensionalVecMap
 //------------------------------------------
 
-#[derive(Debug, Clone, Default)]
-pub struct Dim2VecMap<K: Copy, V> {
-    map: Vec<Vec<V>>,
-    min_max: MinMaxHolder<K, 2>,
+/// A [DimensionalMap] storing its entries densely in a vector, which suits keys filling a box
+///
+/// The vector covers a box of keys starting at an origin, so negative keys are fine. Inserting a
+/// key outside of the box grows it, at least doubling it along each dimension the key lies beyond.
+#[derive(Debug, Clone)]
+pub struct DimensionalVecMap<K, V, const N: usize = 2> {
+    /// Cells of the box, the last dimension varying fastest
+    cells: Vec<Option<V>>,
+    /// Smallest key of the box
+    origin: [isize; N],
+    /// Size of the box along each dimension
+    extent: [usize; N],
+    len: usize,
+    min_max: MinMaxHolder<K, N>,
 }
 
-impl<K, V> MinMax<K, 2> for Dim2VecMap<K, V>
-where
-    K: Copy + Clone + Ord + Default,
-{
-    fn min(&self) -> &[Option<K>; 2] {
+/// A two-dimensional [DimensionalVecMap]
+pub type Dim2VecMap<K, V> = DimensionalVecMap<K, V, 2>;
+
+impl<K, V, const N: usize> Default for DimensionalVecMap<K, V, N> {
+    fn default() -> Self {
+        Self {
+            cells: Vec::new(),
+            origin: [0; N],
+            extent: [0; N],
+            len: 0,
+            min_max: Default::default(),
+        }
+    }
+}
+
+impl<K: PrimInt, V, const N: usize> DimensionalVecMap<K, V, N> {
+    /// Creates a map with room for all keys from `min` to `max`, so filling it never grows it
+    pub fn with_bounds(min: [K; N], max: [K; N]) -> Self {
+        let mut this = Self::default();
+        this.grow(min.map(Self::coord), max.map(Self::coord));
+        this
+    }
+
+    fn coord(k: K) -> isize {
+        k.to_isize().expect("key exceeds isize")
+    }
+
+    fn index(&self, k: &[K; N]) -> Option<usize> {
+        index_of(k.map(Self::coord), &self.origin, &self.extent)
+    }
+
+    fn key(&self, index: usize) -> [K; N] {
+        coords_of(index, &self.origin, &self.extent)
+            .map(|c| K::from(c).expect("coordinate of a key"))
+    }
+
+    /// Grows the box so it reaches from `min` to `max`, which must contain the current box
+    fn grow(&mut self, min: [isize; N], max: [isize; N]) {
+        let extent: [usize; N] = std::array::from_fn(|d| (max[d] - min[d] + 1) as usize);
+        let mut cells: Vec<Option<V>> = Vec::new();
+        cells.resize_with(extent.iter().product(), || None);
+
+        for (index, cell) in std::mem::take(&mut self.cells).into_iter().enumerate() {
+            if cell.is_some() {
+                let coords = coords_of(index, &self.origin, &self.extent);
+                cells[index_of(coords, &min, &extent).unwrap()] = cell;
+            }
+        }
+
+        self.cells = cells;
+        self.origin = min;
+        self.extent = extent;
+    }
+
+    /// Grows the box so it contains `coords`
+    fn grow_to(&mut self, coords: [isize; N]) {
+        if self.cells.is_empty() {
+            self.grow(coords, coords);
+            return;
+        }
+
+        let mut min = self.origin;
+        let mut max: [isize; N] = std::array::from_fn(|d| min[d] + self.extent[d] as isize - 1);
+        for d in 0..N {
+            let extent = self.extent[d] as isize;
+            if coords[d] < min[d] {
+                min[d] = coords[d].min(min[d] - extent);
+            } else if coords[d] > max[d] {
+                max[d] = coords[d].max(max[d] + extent);
+            }
+        }
+        self.grow(min, max);
+    }
+}
+
+impl<K, V, const N: usize> MinMax<K, N> for DimensionalVecMap<K, V, N> {
+    fn min(&self) -> &[Option<K>; N] {
         self.min_max.min()
     }
 
-    fn max(&self) -> &[Option<K>; 2] {
+    fn max(&self) -> &[Option<K>; N] {
         self.min_max.max()
     }
 }
 
-impl<K, V> DimensionalMap<K, V, 2> for Dim2VecMap<K, V>
-where
-    K: Copy + Clone + Ord + Default + Into<isize>,
-    V: Default,
-{
-    fn insert<U: Into<V>>(&mut self, k: [K; 2], v: U) {
+impl<K: PrimInt, V, const N: usize> DimensionalMap<K, V, N> for DimensionalVecMap<K, V, N> {
+    fn insert<U: Into<V>>(&mut self, k: [K; N], v: U) {
         self.min_max.insert(k);
-        let d1: isize = k[0].into();
-        if d1 < 0 {
-            return;
-        }
-        let d2: isize = k[1].into();
-        if d2 < 0 {
-            return;
+        let index = match self.index(&k) {
+            Some(index) => index,
+            None => {
+                self.grow_to(k.map(Self::coord));
+                self.index(&k).unwrap()
+            }
+        };
+
+        if self.cells[index].replace(v.into()).is_none() {
+            self.len += 1;
         }
+    }
 
-        if self.map.get(d1 as usize).is_none() {
-            self.map[d1 as usize] = Vec::with_capacity(d2 as usize);
-        };
-        self.map[d1 as usize][d2 as usize] = v.into();
+    fn get<U: Borrow<[K; N]>>(&self, k: &U) -> Option<&V> {
+        self.index(k.borrow())
+            .and_then(|index| self.cells[index].as_ref())
     }
 
-    fn get<U: Borrow<[K; 2]>>(&self, k: &U) -> Option<&V> {
-        let k = k.borrow();
-        let d1: isize = k[0].into();
-        if d1 < 0 {
-            return None;
-        }
-        let d2: isize = k[1].into();
-        if d2 < 0 {
-            return None;
-        }
-        self.map[d1 as usize].get(d2 as usize)
+    fn get_mut<U: Borrow<[K; N]>>(&mut self, k: &U) -> Option<&mut V> {
+        self.index(k.borrow())
+            .and_then(|index| self.cells[index].as_mut())
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter<'a>(&'a self) -> impl Iterator<Item = ([K; N], &'a V)>
+    where
+        V: 'a,
+    {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter_map(|(index, cell)| cell.as_ref().map(|v| (self.key(index), v)))
     }
 }
 
-impl<K, V> FromIterator<([K; 2], V)> for Dim2VecMap<K, V>
-where
-    K: Default + Copy + Clone + Ord + Into<isize>,
-    V: Default,
-{
-    fn from_iter<I: IntoIterator<Item = ([K; 2], V)>>(iter: I) -> Self {
+impl<K: PrimInt, V, const N: usize> FromIterator<([K; N], V)> for DimensionalVecMap<K, V, N> {
+    fn from_iter<I: IntoIterator<Item = ([K; N], V)>>(iter: I) -> Self {
         let mut this = Self::default();
         for (k, v) in iter {
             this.insert(k, v);
@@ -134,14 +258,58 @@ where
     }
 }
 
+/// Index of `coords` into the cells of a box, if the box contains them
+fn index_of<const N: usize>(
+    coords: [isize; N],
+    origin: &[isize; N],
+    extent: &[usize; N],
+) -> Option<usize> {
+    let mut index = 0;
+    for d in 0..N {
+        let offset = coords[d] - origin[d];
+        if offset < 0 || offset as usize >= extent[d] {
+            return None;
+        }
+        index = index * extent[d] + offset as usize;
+    }
+    Some(index)
+}
+
+/// Coordinates of the cell at `index` of a box
+fn coords_of<const N: usize>(
+    mut index: usize,
+    origin: &[isize; N],
+    extent: &[usize; N],
+) -> [isize; N] {
+    let mut coords = [0; N];
+    for d in (0..N).rev() {
+        coords[d] = origin[d] + (index % extent[d]) as isize;
+        index /= extent[d];
+    }
+    coords
+}
+
 //------------------------------------------
 // MinMax
 //------------------------------------------
 
+/// The smallest and largest key along each dimension
 pub trait MinMax<T, const N: usize> {
     fn min(&self) -> &[Option<T>; N];
 
     fn max(&self) -> &[Option<T>; N];
+
+    /// The inclusive ranges of the keys along each dimension, unless there are no keys
+    fn bounds(&self) -> Option<[RangeInclusive<T>; N]>
+    where
+        T: Copy,
+    {
+        let (min, max) = (self.min(), self.max());
+        if min.iter().chain(max).any(Option::is_none) {
+            return None;
+        }
+        Some(std::array::from_fn(|d| min[d].unwrap()..=max[d].unwrap()))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -172,16 +340,16 @@ impl<T: Clone + Ord, const N: usize> MinMaxHolder<T, N> {
     }
 }
 
-impl<T: Copy + Default, const N: usize> Default for MinMaxHolder<T, N> {
+impl<T, const N: usize> Default for MinMaxHolder<T, N> {
     fn default() -> Self {
         Self {
-            min: [Default::default(); N],
-            max: [Default::default(); N],
+            min: std::array::from_fn(|_| None),
+            max: std::array::from_fn(|_| None),
         }
     }
 }
 
-impl<T: Clone + Ord, const N: usize> MinMax<T, N> for MinMaxHolder<T, N> {
+impl<T, const N: usize> MinMax<T, N> for MinMaxHolder<T, N> {
     fn min(&self) -> &[Option<T>; N] {
         &self.min
     }
@@ -190,3 +358,45 @@ impl<T: Clone + Ord, const N: usize> MinMax<T, N> for MinMaxHolder<T, N> {
         &self.max
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn check<M: DimensionalMap<i16, char>>() {
+        let mut map: M = [([0, 0], 'a'), ([2, -3], 'b')].into_iter().collect();
+        map.insert([-4, 1], 'c');
+        map.insert([2, -3], 'd');
+        *map.get_mut(&[0, 0]).unwrap() = 'e';
+
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.get(&[0, 0]), Some(&'e'));
+        assert_eq!(map.get(&[2, -3]), Some(&'d'));
+        assert_eq!(map.get(&[-4, 1]), Some(&'c'));
+        assert_eq!(map.get(&[1, 0]), None);
+        assert_eq!(map.get(&[9, 9]), None);
+        assert_eq!(map.bounds(), Some([-4..=2, -3..=1]));
+
+        let mut entries = map.iter().map(|(k, v)| (k, *v)).collect::<Vec<_>>();
+        entries.sort();
+        assert_eq!(entries, [([-4, 1], 'c'), ([0, 0], 'e'), ([2, -3], 'd')]);
+    }
+
+    #[test]
+    fn hash_map() {
+        check::<DimensionalHashMap<i16, char>>();
+    }
+
+    #[test]
+    fn vec_map() {
+        check::<Dim2VecMap<i16, char>>();
+
+        let mut map = DimensionalVecMap::<i32, u8, 3>::with_bounds([-1, -1, -1], [1, 1, 1]);
+        assert_eq!(map.bounds(), None);
+        map.insert([-1, 0, 1], 1);
+        map.insert([5, -7, 0], 2);
+        assert_eq!(map.get(&[-1, 0, 1]), Some(&1));
+        assert_eq!(map.get(&[5, -7, 0]), Some(&2));
+        assert_eq!(map.bounds(), Some([-1..=5, -7..=0, 0..=1]));
+    }
+}
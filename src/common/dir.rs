@@ -1,6 +1,8 @@
 use num::{Integer, One, Zero};
 use std::ops::Neg;
 
+use super::vector::Vector;
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Dir {
     #[default]
@@ -13,14 +15,16 @@ pub enum Dir {
 impl Dir {
     pub const ALL: [Self; 4] = [Self::N, Self::E, Self::S, Self::W];
 
-    pub fn go<N: Integer + Neg<Output = N>>(&self, (y, x): (N, N)) -> (N, N) {
-        let dir: (N, N) = <Dir as Into<(N, N)>>::into(*self) as (N, N);
-        (y + dir.0, x + dir.1)
+    pub fn go<N: Integer + Neg<Output = N> + Copy>(&self, pos: Vector<N, 2>) -> Vector<N, 2> {
+        pos + Vector::from(*self)
     }
 
-    pub fn go_n<N: Integer + Neg<Output = N> + Copy>(&self, (y, x): (N, N), n: N) -> (N, N) {
-        let dir: (N, N) = <Dir as Into<(N, N)>>::into(*self) as (N, N);
-        (y + dir.0 * n, x + dir.1 * n)
+    pub fn go_n<N: Integer + Neg<Output = N> + Copy>(
+        &self,
+        pos: Vector<N, 2>,
+        n: N,
+    ) -> Vector<N, 2> {
+        pos + Vector::from(*self) * n
     }
 
     pub fn turn_left(&self) -> Self {
@@ -51,13 +55,13 @@ impl Dir {
     }
 }
 
-impl<T: Integer + Neg<Output = T>> From<Dir> for (T, T) {
-    fn from(dir: Dir) -> (T, T) {
+impl<T: Integer + Neg<Output = T>> From<Dir> for Vector<T, 2> {
+    fn from(dir: Dir) -> Vector<T, 2> {
         match dir {
-            Dir::N => (Neg::neg(<T as One>::one()), Zero::zero()),
-            Dir::S => (One::one(), Zero::zero()),
-            Dir::W => (Zero::zero(), Neg::neg(<T as One>::one())),
-            Dir::E => (Zero::zero(), One::one()),
+            Dir::N => Vector([Neg::neg(<T as One>::one()), Zero::zero()]),
+            Dir::S => Vector([One::one(), Zero::zero()]),
+            Dir::W => Vector([Zero::zero(), Neg::neg(<T as One>::one())]),
+            Dir::E => Vector([Zero::zero(), One::one()]),
         }
     }
 }
@@ -85,3 +89,255 @@ impl<'a> TryFrom<&'a str> for Dir {
         result.map_err(|_| value)
     }
 }
+
+/// A unit step of `-1`, `0` or `1` as integer of any type
+fn unit<T: Integer + Neg<Output = T>>(step: i8) -> T {
+    match step {
+        -1 => Neg::neg(T::one()),
+        0 => T::zero(),
+        _ => T::one(),
+    }
+}
+
+/// One of the eight directions on a grid, including the diagonals
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir8 {
+    #[default]
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Dir8 {
+    /// All directions clockwise, starting north
+    pub const ALL: [Self; 8] = [
+        Self::N,
+        Self::NE,
+        Self::E,
+        Self::SE,
+        Self::S,
+        Self::SW,
+        Self::W,
+        Self::NW,
+    ];
+
+    /// The diagonal directions clockwise, starting north-east
+    pub const DIAGONALS: [Self; 4] = [Self::NE, Self::SE, Self::SW, Self::NW];
+
+    pub fn go<N: Integer + Neg<Output = N> + Copy>(&self, pos: Vector<N, 2>) -> Vector<N, 2> {
+        pos + Vector::from(*self)
+    }
+
+    pub fn go_n<N: Integer + Neg<Output = N> + Copy>(
+        &self,
+        pos: Vector<N, 2>,
+        n: N,
+    ) -> Vector<N, 2> {
+        pos + Vector::from(*self) * n
+    }
+
+    /// The direction `steps` eighths of a turn clockwise
+    fn turn(&self, steps: usize) -> Self {
+        Self::ALL[(*self as usize + steps) % 8]
+    }
+
+    pub fn turn_left_45(&self) -> Self {
+        self.turn(7)
+    }
+
+    pub fn turn_right_45(&self) -> Self {
+        self.turn(1)
+    }
+
+    pub fn turn_left(&self) -> Self {
+        self.turn(6)
+    }
+
+    pub fn turn_right(&self) -> Self {
+        self.turn(2)
+    }
+
+    pub fn opposite(&self) -> Self {
+        self.turn(4)
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        Self::DIAGONALS.contains(self)
+    }
+}
+
+impl<T: Integer + Neg<Output = T>> From<Dir8> for Vector<T, 2> {
+    fn from(dir: Dir8) -> Vector<T, 2> {
+        let (dy, dx) = match dir {
+            Dir8::N => (-1, 0),
+            Dir8::NE => (-1, 1),
+            Dir8::E => (0, 1),
+            Dir8::SE => (1, 1),
+            Dir8::S => (1, 0),
+            Dir8::SW => (1, -1),
+            Dir8::W => (0, -1),
+            Dir8::NW => (-1, -1),
+        };
+        Vector([unit(dy), unit(dx)])
+    }
+}
+
+impl From<Dir> for Dir8 {
+    fn from(dir: Dir) -> Self {
+        match dir {
+            Dir::N => Dir8::N,
+            Dir::S => Dir8::S,
+            Dir::W => Dir8::W,
+            Dir::E => Dir8::E,
+        }
+    }
+}
+
+impl TryFrom<Dir8> for Dir {
+    type Error = Dir8;
+
+    fn try_from(value: Dir8) -> Result<Self, Self::Error> {
+        match value {
+            Dir8::N => Ok(Dir::N),
+            Dir8::S => Ok(Dir::S),
+            Dir8::W => Ok(Dir::W),
+            Dir8::E => Ok(Dir::E),
+            _ => Err(value),
+        }
+    }
+}
+
+/// One of the six directions on a hexagonal grid with pointy-topped cells
+///
+/// Positions are axial coordinates `[r, q]`: moving east or west changes `q`, moving south-east or
+/// north-west changes `r`, and moving south-west or north-east changes both.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HexDir {
+    #[default]
+    E,
+    SE,
+    SW,
+    W,
+    NW,
+    NE,
+}
+
+impl HexDir {
+    /// All directions clockwise, starting east
+    pub const ALL: [Self; 6] = [Self::E, Self::SE, Self::SW, Self::W, Self::NW, Self::NE];
+
+    pub fn go<N: Integer + Neg<Output = N> + Copy>(&self, pos: Vector<N, 2>) -> Vector<N, 2> {
+        pos + Vector::from(*self)
+    }
+
+    pub fn go_n<N: Integer + Neg<Output = N> + Copy>(
+        &self,
+        pos: Vector<N, 2>,
+        n: N,
+    ) -> Vector<N, 2> {
+        pos + Vector::from(*self) * n
+    }
+
+    /// The direction `steps` sixths of a turn clockwise
+    fn turn(&self, steps: usize) -> Self {
+        Self::ALL[(*self as usize + steps) % 6]
+    }
+
+    pub fn turn_left(&self) -> Self {
+        self.turn(5)
+    }
+
+    pub fn turn_right(&self) -> Self {
+        self.turn(1)
+    }
+
+    pub fn opposite(&self) -> Self {
+        self.turn(3)
+    }
+
+    /// Number of steps between the axial coordinates `a` and `b`
+    pub fn distance<N: Integer + Neg<Output = N> + Copy>(a: Vector<N, 2>, b: Vector<N, 2>) -> N {
+        let Vector([dr, dq]) = a - b;
+        let abs = |n: N| if n < N::zero() { -n } else { n };
+        (abs(dr) + abs(dq) + abs(dr + dq)) / (N::one() + N::one())
+    }
+}
+
+impl<T: Integer + Neg<Output = T>> From<HexDir> for Vector<T, 2> {
+    fn from(dir: HexDir) -> Vector<T, 2> {
+        let (dr, dq) = match dir {
+            HexDir::E => (0, 1),
+            HexDir::SE => (1, 0),
+            HexDir::SW => (1, -1),
+            HexDir::W => (0, -1),
+            HexDir::NW => (-1, 0),
+            HexDir::NE => (-1, 1),
+        };
+        Vector([unit(dr), unit(dq)])
+    }
+}
+
+impl<'a> TryFrom<&'a str> for HexDir {
+    type Error = &'a str;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        match value {
+            "e" | "E" => Ok(HexDir::E),
+            "se" | "SE" => Ok(HexDir::SE),
+            "sw" | "SW" => Ok(HexDir::SW),
+            "w" | "W" => Ok(HexDir::W),
+            "nw" | "NW" => Ok(HexDir::NW),
+            "ne" | "NE" => Ok(HexDir::NE),
+            _ => Err(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn dir8() {
+        assert_eq!(Dir8::N.turn_left_45(), Dir8::NW);
+        assert_eq!(Dir8::NW.turn_right_45(), Dir8::N);
+        assert_eq!(Dir8::SE.opposite(), Dir8::NW);
+        for dir in Dir8::ALL {
+            let delta = Vector::<i8, 2>::from(dir);
+            assert_eq!(delta.rotate_right(), dir.turn_right().into());
+            assert_eq!(dir.is_diagonal(), delta.manhattan(Vector([0, 0])) == 2);
+        }
+        for dir in Dir::ALL {
+            let dir8 = Dir8::from(dir);
+            assert_eq!(Dir::try_from(dir8), Ok(dir));
+            assert_eq!(dir8.go(Vector([3_i16, 5])), dir.go(Vector([3, 5])));
+        }
+        assert_eq!(Dir::try_from(Dir8::SW), Err(Dir8::SW));
+        assert_eq!(Dir8::SW.go_n(Vector([0_i64, 0]), 3), Vector([3, -3]));
+    }
+
+    #[test]
+    fn hex_dir() {
+        let origin = Vector([0_i32, 0]);
+        for dir in HexDir::ALL {
+            assert_eq!(HexDir::distance(dir.go(origin), origin), 1);
+            assert_eq!(dir.opposite().go(dir.go(origin)), origin);
+            assert_eq!(dir.turn_right().turn_left(), dir);
+        }
+
+        let path = ["ne", "ne", "s", "sw"].map(HexDir::try_from);
+        assert_eq!(path[2], Err("s"));
+
+        let pos = ["se", "sw", "se", "sw", "sw"]
+            .into_iter()
+            .map(|dir| HexDir::try_from(dir).unwrap())
+            .fold(origin, |pos, dir| dir.go(pos));
+        assert_eq!(pos, Vector([5, -3]));
+        assert_eq!(HexDir::distance(pos, origin), 5);
+    }
+}
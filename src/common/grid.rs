@@ -0,0 +1,276 @@
+//! A dense two-dimensional grid, as most maps of the puzzles are
+
+use std::{
+    fmt::{Display, Write},
+    ops::{Index, IndexMut},
+};
+
+use aoc_runner::ParseError;
+
+use super::vector::Vector;
+
+/// A position in a grid as `[y, x]`
+pub type Coord = Vector<i16, 2>;
+
+/// A rectangular grid of cells stored row by row
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    height: usize,
+    width: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid with every cell set to `value`
+    pub fn new(height: usize, width: usize, value: T) -> Self {
+        Self {
+            height,
+            width,
+            cells: vec![value; height * width],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Parses a grid from the lines of `input`, converting each character with `f`
+    ///
+    /// All lines must have the same number of characters. A character `f` rejects is reported as
+    /// error expecting `expected`.
+    pub fn parse<F>(input: &str, expected: &str, mut f: F) -> Result<Self, ParseError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::with_capacity(input.len());
+        for line in input.lines() {
+            let len = cells.len();
+            for (offset, ch) in line.char_indices() {
+                let cell = f(ch).ok_or_else(|| {
+                    ParseError::at(input, &line[offset..offset + ch.len_utf8()], expected)
+                })?;
+                cells.push(cell);
+            }
+            let line_width = cells.len() - len;
+            // a grid without columns has no cells to address
+            if width.is_none() && line_width == 0 {
+                return Err(ParseError::at(input, line, "a row of cells"));
+            }
+            if *width.get_or_insert(line_width) != line_width {
+                let expected = format!("a row of {} cells", width.unwrap_or_default());
+                return Err(ParseError::at(input, line, expected));
+            }
+            height += 1;
+        }
+
+        let width = width.ok_or_else(|| ParseError::end_of_input(input, "a grid"))?;
+        Ok(Self {
+            height,
+            width,
+            cells,
+        })
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Number of cells
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Whether `coord` lies inside the grid
+    #[inline]
+    pub fn is_inside(&self, Vector([y, x]): Coord) -> bool {
+        y >= 0 && x >= 0 && (y as usize) < self.height && (x as usize) < self.width
+    }
+
+    /// Index of `coord` into the cells stored row by row, e.g. to track cells in a bit set
+    #[inline]
+    pub fn index_of(&self, coord: Coord) -> Option<usize> {
+        self.is_inside(coord)
+            .then(|| coord.y() as usize * self.width + coord.x() as usize)
+    }
+
+    /// Coordinate of the cell at `index` into the cells stored row by row
+    pub fn coord_of(&self, index: usize) -> Coord {
+        Vector([(index / self.width) as i16, (index % self.width) as i16])
+    }
+
+    pub fn get(&self, coord: Coord) -> Option<&T> {
+        self.index_of(coord).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, coord: Coord) -> Option<&mut T> {
+        self.index_of(coord).map(|index| &mut self.cells[index])
+    }
+
+    /// All coordinates, row by row
+    pub fn coords(&self) -> impl Iterator<Item = Coord> + '_ {
+        (0..self.len()).map(|index| self.coord_of(index))
+    }
+
+    /// All cells along with their coordinates, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(index, cell)| (self.coord_of(index), cell))
+    }
+
+    /// All cells, row by row
+    pub fn values(&self) -> impl Iterator<Item = &T> + '_ {
+        self.cells.iter()
+    }
+
+    /// Converts each cell with `f`
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            height: self.height,
+            width: self.width,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// The orthogonal neighbours of `coord` inside the grid
+    pub fn neighbours(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        coord.neighbours().filter(|coord| self.is_inside(*coord))
+    }
+
+    /// The orthogonal and diagonal neighbours of `coord` inside the grid
+    pub fn neighbours8(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        coord
+            .all_neighbours()
+            .filter(|coord| self.is_inside(*coord))
+    }
+
+    /// The cells of row `y`
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// The cells of column `x`, top to bottom
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// The cells from `start` in steps of `step` until leaving the grid
+    pub fn line(&self, start: Coord, step: Coord) -> impl Iterator<Item = &T> + '_ {
+        std::iter::successors(Some(start), move |coord| Some(*coord + step))
+            .map_while(|coord| self.get(coord))
+    }
+
+    /// The cells from `start` down to the right until leaving the grid
+    pub fn diagonal(&self, start: Coord) -> impl Iterator<Item = &T> + '_ {
+        self.line(start, Vector([1, 1]))
+    }
+
+    /// The cells from `start` down to the left until leaving the grid
+    pub fn anti_diagonal(&self, start: Coord) -> impl Iterator<Item = &T> + '_ {
+        self.line(start, Vector([1, -1]))
+    }
+
+    /// Coordinate of the first cell, row by row, `f` returns true for
+    pub fn position<F: FnMut(&T) -> bool>(&self, f: F) -> Option<Coord> {
+        self.cells
+            .iter()
+            .position(f)
+            .map(|index| self.coord_of(index))
+    }
+
+    /// Coordinate of the first cell, row by row, equal to `value`
+    pub fn find(&self, value: &T) -> Option<Coord>
+    where
+        T: PartialEq,
+    {
+        self.position(|cell| cell == value)
+    }
+}
+
+impl TryFrom<&str> for Grid<char> {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::parse(value, "a character", Some)
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coord: Coord) -> &Self::Output {
+        self.get(coord)
+            .unwrap_or_else(|| panic!("{:?} is outside of the grid", coord))
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, coord: Coord) -> &mut Self::Output {
+        self.get_mut(coord)
+            .unwrap_or_else(|| panic!("{:?} is outside of the grid", coord))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height {
+            for cell in self.row(y) {
+                write!(f, "{}", cell)?;
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn grid() {
+        let input = "ab.\n.cd\n";
+        let grid = Grid::try_from(input).unwrap();
+        assert_eq!((grid.height(), grid.width()), (2, 3));
+        assert_eq!(grid[Vector([1, 2])], 'd');
+        assert_eq!(grid.get(Vector([2, 0])), None);
+        assert_eq!(grid.find(&'c'), Some(Vector([1, 1])));
+        assert_eq!(grid.to_string(), input);
+
+        assert_eq!(grid.row(1), ['.', 'c', 'd']);
+        assert_eq!(grid.column(1).collect::<String>(), "bc");
+        assert_eq!(grid.diagonal(Vector([0, 0])).collect::<String>(), "ac");
+        assert_eq!(grid.anti_diagonal(Vector([0, 2])).collect::<String>(), ".c");
+
+        assert_eq!(
+            grid.neighbours(Vector([0, 0])).collect::<Vec<_>>(),
+            [Vector([1, 0]), Vector([0, 1])]
+        );
+        assert_eq!(grid.neighbours8(Vector([0, 0])).count(), 3);
+        assert_eq!(grid.neighbours8(Vector([1, 1])).count(), 5);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = Grid::parse("12\n3x\n", "a digit", |ch| ch.to_digit(10)).unwrap_err();
+        assert_eq!((error.line(), error.column()), (2, 2));
+        assert_eq!(error.expected(), "a digit");
+
+        let error = Grid::try_from("abc\nde\n").unwrap_err();
+        assert_eq!((error.line(), error.column()), (2, 1));
+        assert_eq!(error.expected(), "a row of 3 cells");
+
+        let error = Grid::try_from("\n").unwrap_err();
+        assert_eq!((error.line(), error.column()), (1, 1));
+        assert_eq!(error.expected(), "a row of cells");
+    }
+}
@@ -3,6 +3,8 @@ pub mod chinese_remainder;
 pub mod debug;
 pub mod dimensional_map;
 pub mod dir;
+pub mod grid;
 pub mod iter;
+pub mod search;
 pub mod transform;
-pub mod v2;
+pub mod vector;
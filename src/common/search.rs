@@ -0,0 +1,381 @@
+//! Graph searches over nodes whose successors are given by a closure
+//!
+//! Besides the cost of each reached node, the searches record every predecessor on a cheapest path
+//! to it. Those form a DAG (as long as all steps cost more than zero), from which single paths, all
+//! cheapest paths or all nodes on them are recovered.
+
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BinaryHeap, VecDeque},
+    hash::Hash,
+};
+
+use fxhash::{FxHashMap, FxHashSet};
+use num::Zero;
+
+/// The outcome of a search
+#[derive(Debug, Clone)]
+pub struct Search<N, C> {
+    /// Cost of the cheapest path to each reached node
+    costs: FxHashMap<N, C>,
+    /// The predecessors of each reached node on its cheapest paths, empty for the start nodes
+    predecessors: FxHashMap<N, Vec<N>>,
+    /// The goals reached at the cost of the cheapest goal
+    goals: Vec<N>,
+    goal_cost: Option<C>,
+}
+
+impl<N, C> Default for Search<N, C> {
+    fn default() -> Self {
+        Self {
+            costs: Default::default(),
+            predecessors: Default::default(),
+            goals: Vec::new(),
+            goal_cost: None,
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash, C: Copy + Ord> Search<N, C> {
+    fn start(&mut self, node: N, cost: C) {
+        self.costs.insert(node.clone(), cost);
+        self.predecessors.entry(node).or_default();
+    }
+
+    /// Records reaching `next` from `node` at `cost`, returning whether it is cheaper than before
+    fn relax(&mut self, node: &N, next: N, cost: C) -> bool {
+        match self.costs.get(&next) {
+            Some(known) if cost > *known => false,
+            Some(known) if cost == *known => {
+                self.predecessors
+                    .entry(next)
+                    .or_default()
+                    .push(node.clone());
+                false
+            }
+            _ => {
+                self.costs.insert(next.clone(), cost);
+                self.predecessors.insert(next, vec![node.clone()]);
+                true
+            }
+        }
+    }
+
+    /// Records `node` as goal if no cheaper goal was reached yet
+    fn reach_goal(&mut self, node: N, cost: C) {
+        if *self.goal_cost.get_or_insert(cost) == cost {
+            self.goals.push(node);
+        }
+    }
+
+    /// Whether a node popped at `priority` can't be on a path as cheap as the goals
+    fn is_done(&self, priority: C) -> bool {
+        self.goal_cost.is_some_and(|goal_cost| priority > goal_cost)
+    }
+
+    /// Cost of the cheapest path to `node`, if it was reached
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.costs.get(node).copied()
+    }
+
+    /// The costs of all reached nodes
+    pub fn costs(&self) -> &FxHashMap<N, C> {
+        &self.costs
+    }
+
+    /// The goals reached at the cost of the cheapest goal, in the order they were reached
+    pub fn goals(&self) -> &[N] {
+        &self.goals
+    }
+
+    /// Cost of the cheapest goal, if one was reached
+    pub fn goal_cost(&self) -> Option<C> {
+        self.goal_cost
+    }
+
+    /// The predecessors of `node` on its cheapest paths
+    pub fn predecessors(&self, node: &N) -> &[N] {
+        self.predecessors.get(node).map_or(&[], Vec::as_slice)
+    }
+
+    /// A cheapest path from a start node to `node`, including both
+    pub fn path(&self, node: &N) -> Option<Vec<N>> {
+        self.costs.get(node)?;
+        let mut path = vec![node.clone()];
+        while let Some(predecessor) = self.predecessors(path.last().unwrap()).first() {
+            path.push(predecessor.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// All cheapest paths from a start node to `node`, including both
+    pub fn all_paths(&self, node: &N) -> Vec<Vec<N>> {
+        if !self.costs.contains_key(node) {
+            return Vec::new();
+        }
+        let predecessors = self.predecessors(node);
+        if predecessors.is_empty() {
+            return vec![vec![node.clone()]];
+        }
+        predecessors
+            .iter()
+            .flat_map(|predecessor| self.all_paths(predecessor))
+            .map(|mut path| {
+                path.push(node.clone());
+                path
+            })
+            .collect()
+    }
+
+    /// Number of cheapest paths from a start node to `node`
+    pub fn count_paths(&self, node: &N) -> usize {
+        fn count<N: Clone + Eq + Hash, C: Copy + Ord>(
+            search: &Search<N, C>,
+            node: &N,
+            counts: &mut FxHashMap<N, usize>,
+        ) -> usize {
+            if let Some(n) = counts.get(node) {
+                return *n;
+            }
+            let predecessors = search.predecessors(node);
+            let n = if predecessors.is_empty() {
+                1
+            } else {
+                predecessors
+                    .iter()
+                    .map(|predecessor| count(search, predecessor, counts))
+                    .sum()
+            };
+            counts.insert(node.clone(), n);
+            n
+        }
+
+        if !self.costs.contains_key(node) {
+            return 0;
+        }
+        count(self, node, &mut Default::default())
+    }
+
+    /// All nodes on any cheapest path to one of `nodes`
+    pub fn nodes_on_paths(&self, nodes: impl IntoIterator<Item = N>) -> FxHashSet<N> {
+        let mut visited: FxHashSet<N> = Default::default();
+        let mut agenda: VecDeque<N> = nodes
+            .into_iter()
+            .filter(|node| self.costs.contains_key(node))
+            .collect();
+        while let Some(node) = agenda.pop_front() {
+            if visited.insert(node.clone()) {
+                agenda.extend(self.predecessors(&node).iter().cloned());
+            }
+        }
+        visited
+    }
+}
+
+/// Searches breadth first from `starts`, where every step costs 1
+///
+/// The search stops once all goals as close as the closest goal are found, or explores all nodes
+/// reachable if there is none.
+pub fn bfs<N, S, I, G>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: S,
+    mut is_goal: G,
+) -> Search<N, usize>
+where
+    N: Clone + Eq + Hash,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+    G: FnMut(&N) -> bool,
+{
+    let mut search = Search::default();
+    let mut agenda = VecDeque::new();
+    for start in starts {
+        search.start(start.clone(), 0);
+        agenda.push_back((start, 0));
+    }
+
+    while let Some((node, cost)) = agenda.pop_front() {
+        if search.is_done(cost) {
+            break;
+        }
+        if is_goal(&node) {
+            search.reach_goal(node, cost);
+            continue;
+        }
+        for next in successors(&node) {
+            if search.relax(&node, next.clone(), cost + 1) {
+                agenda.push_back((next, cost + 1));
+            }
+        }
+    }
+
+    search
+}
+
+/// Searches the cheapest paths from `starts`, where `successors` yields each next node along with
+/// the cost of the step to it
+///
+/// The search stops once all goals as cheap as the cheapest goal are found, or explores all nodes
+/// reachable if there is none.
+pub fn dijkstra<N, C, S, I, G>(
+    starts: impl IntoIterator<Item = N>,
+    successors: S,
+    is_goal: G,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    G: FnMut(&N) -> bool,
+{
+    astar(starts, successors, |_| C::zero(), is_goal)
+}
+
+/// Searches the cheapest paths from `starts` like [dijkstra], exploring the nodes in the order of
+/// their cost plus the estimate of `heuristic`
+///
+/// The costs are only exact if the heuristic never overestimates the remaining cost and never
+/// drops by more than the cost of a step.
+pub fn astar<N, C, S, I, H, G>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: S,
+    mut heuristic: H,
+    mut is_goal: G,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Zero,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    H: FnMut(&N) -> C,
+    G: FnMut(&N) -> bool,
+{
+    let mut search = Search::default();
+    let mut agenda = BinaryHeap::new();
+    for start in starts {
+        search.start(start.clone(), C::zero());
+        agenda.push(Reverse(Entry {
+            priority: heuristic(&start),
+            cost: C::zero(),
+            node: start,
+        }));
+    }
+
+    while let Some(Reverse(Entry {
+        priority,
+        cost,
+        node,
+    })) = agenda.pop()
+    {
+        if search.is_done(priority) {
+            break;
+        }
+        // a cheaper path to the node was found after this entry was pushed
+        if search.cost(&node).is_some_and(|known| cost > known) {
+            continue;
+        }
+        if is_goal(&node) {
+            search.reach_goal(node, cost);
+            continue;
+        }
+        for (next, step) in successors(&node) {
+            let cost = cost + step;
+            if search.relax(&node, next.clone(), cost) {
+                agenda.push(Reverse(Entry {
+                    priority: cost + heuristic(&next),
+                    cost,
+                    node: next,
+                }));
+            }
+        }
+    }
+
+    search
+}
+
+/// An entry of the agenda, ordered by its priority only
+struct Entry<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for Entry<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for Entry<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Entry<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Entry<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority.cmp(&other.priority)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A diamond `0 -> 1 | 2 -> 3` followed by a chain `3 -> 4 -> 5`
+    fn successors(node: &u8) -> Vec<u8> {
+        match node {
+            0 => vec![1, 2],
+            1 | 2 => vec![3],
+            3 => vec![4],
+            4 => vec![5],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn bfs_paths() {
+        let search = bfs([0], successors, |node| *node == 4);
+        assert_eq!(search.goal_cost(), Some(3));
+        assert_eq!(search.goals(), [4]);
+        assert_eq!(search.cost(&5), None);
+        assert_eq!(search.path(&4), Some(vec![0, 1, 3, 4]));
+        assert_eq!(search.all_paths(&4), [[0, 1, 3, 4], [0, 2, 3, 4]]);
+        assert_eq!(search.count_paths(&4), 2);
+        assert_eq!(search.nodes_on_paths([3]).len(), 4);
+
+        let search = bfs([0], successors, |_| false);
+        assert_eq!(search.cost(&5), Some(4));
+        assert_eq!(search.costs().len(), 6);
+    }
+
+    #[test]
+    fn weighted_paths() {
+        // the direct step from 0 to 3 costs as much as the detours
+        let successors = |node: &u8| {
+            let mut next: Vec<(u8, u32)> = successors(node).into_iter().map(|n| (n, 5)).collect();
+            if *node == 0 {
+                next.push((3, 10));
+            }
+            next
+        };
+
+        let search = dijkstra([0], successors, |node| *node == 5);
+        assert_eq!(search.goal_cost(), Some(20));
+        assert_eq!(search.count_paths(&5), 3);
+        assert_eq!(search.predecessors(&3).len(), 3);
+
+        let search = astar(
+            [0],
+            successors,
+            |node| 5 * (5 - *node as u32).min(3),
+            |node| *node == 5,
+        );
+        assert_eq!(search.goal_cost(), Some(20));
+        assert_eq!(search.count_paths(&5), 3);
+    }
+}
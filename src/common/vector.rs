@@ -0,0 +1,284 @@
+//! A vector of any dimension, used for positions and offsets
+
+use std::{
+    iter::Sum,
+    ops::{Add, AddAssign, Div, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use num::{traits::Euclid, One, Zero};
+
+/// A vector of `N` components
+///
+/// Two-dimensional positions on a map are stored as `[y, x]`, so they order row by row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vector<T, const N: usize>(pub [T; N]);
+
+/// A two-dimensional [Vector] as `[y, x]`
+pub type Vector2<T> = Vector<T, 2>;
+
+impl<T: Default, const N: usize> Default for Vector<T, N> {
+    fn default() -> Self {
+        Self(std::array::from_fn(|_| T::default()))
+    }
+}
+
+impl<T: Copy, const N: usize> Vector<T, N> {
+    /// Converts each component with `f`
+    pub fn map<U, F: FnMut(T) -> U>(self, f: F) -> Vector<U, N> {
+        Vector(self.0.map(f))
+    }
+
+    /// Combines the components of `self` and `other` with `f`
+    fn zip_with<U, F: FnMut(T, T) -> U>(self, other: Self, mut f: F) -> Vector<U, N> {
+        Vector(std::array::from_fn(|d| f(self.0[d], other.0[d])))
+    }
+}
+
+impl<T: Copy> Vector<T, 2> {
+    pub fn y(&self) -> T {
+        self.0[0]
+    }
+
+    pub fn x(&self) -> T {
+        self.0[1]
+    }
+}
+
+impl<T: Copy + Zero, const N: usize> Vector<T, N> {
+    pub fn zero() -> Self {
+        Self([T::zero(); N])
+    }
+}
+
+impl<T, const N: usize> Vector<T, N>
+where
+    T: Copy + Ord + Zero + Sub<Output = T>,
+{
+    /// The absolute difference of each component
+    pub fn abs_diff(self, other: Self) -> Self {
+        self.zip_with(other, |a, b| if a > b { a - b } else { b - a })
+    }
+
+    /// The distance to `other` moving along the axes only
+    pub fn manhattan(self, other: Self) -> T {
+        self.abs_diff(other)
+            .0
+            .into_iter()
+            .fold(T::zero(), |sum, d| sum + d)
+    }
+
+    /// The distance to `other` moving along the axes and diagonals
+    pub fn chebyshev(self, other: Self) -> T {
+        self.abs_diff(other).0.into_iter().fold(T::zero(), T::max)
+    }
+}
+
+impl<T, const N: usize> Vector<T, N>
+where
+    T: Copy + One + Add<Output = T> + Sub<Output = T>,
+{
+    /// The `2 * N` neighbours along the axes
+    pub fn neighbours(self) -> impl Iterator<Item = Self> {
+        (0..N).flat_map(move |d| {
+            let mut lower = self;
+            let mut upper = self;
+            lower.0[d] = lower.0[d] - T::one();
+            upper.0[d] = upper.0[d] + T::one();
+            [lower, upper]
+        })
+    }
+
+    /// The `3^N - 1` neighbours along the axes and diagonals
+    pub fn all_neighbours(self) -> impl Iterator<Item = Self> {
+        let count = 3_usize.pow(N as u32);
+        (0..count)
+            .filter(move |&i| i != count / 2)
+            .map(move |mut i| {
+                let mut neighbour = self;
+                for c in neighbour.0.iter_mut().rev() {
+                    match i % 3 {
+                        0 => *c = *c - T::one(),
+                        2 => *c = *c + T::one(),
+                        _ => {}
+                    }
+                    i /= 3;
+                }
+                neighbour
+            })
+    }
+}
+
+impl<T: Copy + Neg<Output = T>> Vector<T, 2> {
+    /// Rotated by a quarter turn clockwise, with y pointing down
+    pub fn rotate_right(self) -> Self {
+        let [y, x] = self.0;
+        Self([x, -y])
+    }
+
+    /// Rotated by a quarter turn counterclockwise, with y pointing down
+    pub fn rotate_left(self) -> Self {
+        let [y, x] = self.0;
+        Self([-x, y])
+    }
+}
+
+impl<T: Copy + Euclid, const N: usize> Vector<T, N> {
+    /// Wraps each component into `0..size`, where `size` holds the size of each dimension
+    pub fn rem_euclid(self, size: Self) -> Self {
+        self.zip_with(size, |a, m| a.rem_euclid(&m))
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for Vector<T, N> {
+    fn from(components: [T; N]) -> Self {
+        Self(components)
+    }
+}
+
+impl<T, const N: usize> From<Vector<T, N>> for [T; N] {
+    fn from(vector: Vector<T, N>) -> Self {
+        vector.0
+    }
+}
+
+impl<T> From<(T, T)> for Vector<T, 2> {
+    fn from((y, x): (T, T)) -> Self {
+        Self([y, x])
+    }
+}
+
+impl<T> From<Vector<T, 2>> for (T, T) {
+    fn from(Vector([y, x]): Vector<T, 2>) -> Self {
+        (y, x)
+    }
+}
+
+impl<T, const N: usize> Index<usize> for Vector<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for Vector<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.0[index]
+    }
+}
+
+impl<T: Copy + Add<Output = T>, const N: usize> Add for Vector<T, N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, T::add)
+    }
+}
+
+impl<T: Copy + Add<Output = T>, const N: usize> AddAssign for Vector<T, N> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Copy + Sub<Output = T>, const N: usize> Sub for Vector<T, N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, T::sub)
+    }
+}
+
+impl<T: Copy + Sub<Output = T>, const N: usize> SubAssign for Vector<T, N> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Copy + Neg<Output = T>, const N: usize> Neg for Vector<T, N> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.map(T::neg)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>, const N: usize> Mul<T> for Vector<T, N> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        self.map(|c| c * rhs)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>, const N: usize> MulAssign<T> for Vector<T, N> {
+    fn mul_assign(&mut self, rhs: T) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T: Copy + Div<Output = T>, const N: usize> Div<T> for Vector<T, N> {
+    type Output = Self;
+
+    fn div(self, rhs: T) -> Self::Output {
+        self.map(|c| c / rhs)
+    }
+}
+
+impl<T: Copy + Zero, const N: usize> Sum for Vector<T, N> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), Add::add)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Vector([1, -2]);
+        let b = Vector([-3, 5]);
+        assert_eq!(a + b, Vector([-2, 3]));
+        assert_eq!(a - b, Vector([4, -7]));
+        assert_eq!(-a * 3, Vector([-3, 6]));
+        assert_eq!(Vector([7, -9]) / 2, Vector([3, -4]));
+        assert_eq!(
+            [a, b, a].into_iter().sum::<Vector<i32, 2>>(),
+            Vector([-1, 1])
+        );
+
+        assert_eq!(a.manhattan(b), 11);
+        assert_eq!(a.chebyshev(b), 7);
+        assert_eq!(Vector([3_u8, 1]).manhattan(Vector([1, 4])), 5);
+        assert_eq!(Vector([-1, 7]).rem_euclid(Vector([5, 3])), Vector([4, 1]));
+    }
+
+    #[test]
+    fn rotation() {
+        let north = Vector([-1, 0]);
+        assert_eq!(north.rotate_right(), Vector([0, 1]));
+        assert_eq!(north.rotate_left(), Vector([0, -1]));
+        assert_eq!(north.rotate_right().rotate_right(), -north);
+    }
+
+    #[test]
+    fn neighbours() {
+        let v = Vector([0_i8, 0]);
+        assert_eq!(
+            v.neighbours().collect::<Vec<_>>(),
+            [
+                Vector([-1, 0]),
+                Vector([1, 0]),
+                Vector([0, -1]),
+                Vector([0, 1])
+            ]
+        );
+        assert_eq!(v.all_neighbours().count(), 8);
+        assert!(!v.all_neighbours().any(|n| n == v));
+        assert_eq!(Vector([0_i8; 3]).all_neighbours().count(), 26);
+        assert!(Vector([0_i8; 3])
+            .all_neighbours()
+            .all(|n| n.chebyshev(Vector([0; 3])) == 1));
+    }
+}
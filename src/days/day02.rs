@@ -5,7 +5,7 @@ use std::{borrow::Borrow, cmp::Ordering};
 use itertools::Itertools;
 
 use crate::common::{iter::SkipNth, transform::Transform};
-use aoc_runner::Day;
+use aoc_runner::{Day, ParseError, ParseSlice, SolveError};
 
 type Level = u32;
 type Report = Vec<Level>;
@@ -41,26 +41,29 @@ impl Day for Day02 {
     type Result1 = u32;
     type Result2 = u32;
 
-    fn parse(&mut self, input: &str) {
+    fn parse(&mut self, input: &str) -> Result<(), ParseError> {
         self.reports = input
             .lines()
             .map(|line| {
                 line.split_whitespace()
-                    .map(|part| part.parse().unwrap())
+                    .map(|part| part.parse_at(input))
                     .collect()
             })
-            .collect()
+            .collect::<Result<_, _>>()?;
+        Ok(())
     }
 
-    fn part1(&mut self) -> Self::Result1 {
-        self.reports
+    fn part1(&mut self) -> Result<Self::Result1, SolveError> {
+        Ok(self
+            .reports
             .iter()
             .filter(|r| Safety::new(r.iter()).is_safe())
-            .count() as Self::Result1
+            .count() as Self::Result1)
     }
 
-    fn part2(&mut self) -> Self::Result2 {
-        self.reports
+    fn part2(&mut self) -> Result<Self::Result2, SolveError> {
+        Ok(self
+            .reports
             .iter()
             .filter(|r| {
                 (0..r.len()).any(|i| {
@@ -69,36 +72,6 @@ impl Day for Day02 {
                         .transform(|it| Safety::new(it).is_safe())
                 })
             })
-            .count() as Self::Result1
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use indoc::indoc;
-
-    use super::*;
-
-    const INPUT: &str = indoc! {"
-        7 6 4 2 1
-        1 2 7 8 9
-        9 7 6 2 1
-        1 3 2 4 5
-        8 6 4 4 1
-        1 3 6 7 9
-    "};
-
-    #[test]
-    fn part_1() {
-        let mut day = Day02::default();
-        day.parse(INPUT);
-        assert_eq!(day.part1(), 2);
-    }
-
-    #[test]
-    fn part_2() {
-        let mut day = Day02::default();
-        day.parse(INPUT);
-        assert_eq!(day.part2(), 4);
+            .count() as Self::Result1)
     }
 }
@@ -2,55 +2,42 @@
 
 use std::sync::Arc;
 
-use aoc_runner::Day;
+use aoc_runner::{Day, ParseError, SolveError};
 use bit_set::BitSet;
 use fxhash::FxHashMap as HashMap;
 use fxhash::FxHashSet as HashSet;
 use rayon::iter::IntoParallelRefIterator;
 use rayon::iter::ParallelIterator;
 
-use crate::common::dir::Dir;
-
-type Coord = (i16, i16);
+use crate::common::{
+    dir::Dir,
+    grid::{Coord, Grid},
+};
 
 #[derive(Default, Clone)]
 pub struct Day06 {
     start_pos: Coord,
-    obstacles: HashSet<Coord>,
-    size: [i16; 2],
+    /// Whether a cell holds an obstacle
+    obstacles: Grid<bool>,
 }
 
 impl Day for Day06 {
     type Result1 = u32;
     type Result2 = u32;
 
-    fn parse(&mut self, input: &str) {
-        self.size = {
-            let y = input.lines().count();
-            let x = input.lines().next().unwrap().chars().count();
-            [y as i16, x as i16]
-        };
-
-        input
-            .lines()
-            .enumerate()
-            .flat_map(|(y, line)| {
-                line.chars()
-                    .enumerate()
-                    .map(move |(x, ch)| ((y as i16, x as i16), ch))
-            })
-            .for_each(|(pos, ch)| match ch {
-                '#' => {
-                    self.obstacles.insert(pos);
-                }
-                '^' => {
-                    self.start_pos = pos;
-                }
-                _ => {}
-            });
+    fn parse(&mut self, input: &str) -> Result<(), ParseError> {
+        let map = Grid::parse(input, "a map cell", |ch| match ch {
+            '.' | '#' | '^' => Some(ch),
+            _ => None,
+        })?;
+        self.start_pos = map
+            .find(&'^')
+            .ok_or_else(|| ParseError::end_of_input(input, "a guard"))?;
+        self.obstacles = map.map(|ch| *ch == '#');
+        Ok(())
     }
 
-    fn part1(&mut self) -> Self::Result1 {
+    fn part1(&mut self) -> Result<Self::Result1, SolveError> {
         let mut visited: HashSet<Coord> = Default::default();
         let mut direction = Dir::N;
         let mut pos = self.start_pos;
@@ -58,20 +45,20 @@ impl Day for Day06 {
             visited.insert(pos);
             let next_pos = direction.go(pos);
 
-            if self.outside_map(&next_pos) {
+            if !self.obstacles.is_inside(next_pos) {
                 break;
             }
 
-            if self.obstacles.contains(&next_pos) {
+            if self.obstacles[next_pos] {
                 direction = direction.turn_right();
             } else {
                 pos = next_pos;
             }
         }
-        visited.len() as <Self as Day>::Result1
+        Ok(visited.len() as <Self as Day>::Result1)
     }
 
-    fn part2(&mut self) -> Self::Result2 {
+    fn part2(&mut self) -> Result<Self::Result2, SolveError> {
         let mut direction = Dir::N;
         let mut visited: HashSet<Coord> = Default::default();
         let mut pos = self.start_pos;
@@ -79,11 +66,11 @@ impl Day for Day06 {
             visited.insert(pos);
             let next_pos = direction.go(pos);
 
-            if self.outside_map(&next_pos) {
+            if !self.obstacles.is_inside(next_pos) {
                 break;
             }
 
-            if self.obstacles.contains(&next_pos) {
+            if self.obstacles[next_pos] {
                 direction = direction.turn_right();
             } else {
                 pos = next_pos;
@@ -92,10 +79,10 @@ impl Day for Day06 {
 
         let jump_table = Arc::new(self.init_jump_table());
 
-        visited
+        Ok(visited
             .par_iter()
             .filter(|pos| self.walk(self.start_pos, Dir::N, **pos, Arc::clone(&jump_table)))
-            .count() as <Self as Day>::Result2
+            .count() as <Self as Day>::Result2)
     }
 }
 
@@ -109,10 +96,10 @@ impl Day06 {
         obstacle: Coord,
         jump_table: Arc<JumpTable>,
     ) -> bool {
-        let mut visited = BitSet::with_capacity(self.size[0] as usize * self.size[1] as usize * 4);
+        let mut visited = BitSet::with_capacity(self.obstacles.len() * 4);
 
         let to_index = |pos: Coord, dir: Dir| {
-            4 * (pos.0 as usize * self.size[0] as usize + pos.1 as usize)
+            4 * self.obstacles.index_of(pos).unwrap()
                 + (match dir {
                     Dir::N => 0,
                     Dir::S => 1,
@@ -126,7 +113,7 @@ impl Day06 {
         loop {
             let next_pos = direction.go(pos);
 
-            if self.outside_map(&next_pos) {
+            if !self.obstacles.is_inside(next_pos) {
                 return false;
             }
 
@@ -136,8 +123,8 @@ impl Day06 {
             }
             visited.insert(visited_index);
 
-            if self.obstacles.contains(&next_pos) {
-                if pos.0 != obstacle.0 && pos.1 != obstacle.1 {
+            if self.obstacles[next_pos] {
+                if pos.y() != obstacle.y() && pos.x() != obstacle.x() {
                     if let Some(new_state) = jump_table.get(&(pos, direction)) {
                         match new_state {
                             Some(new_state) => {
@@ -164,15 +151,11 @@ impl Day06 {
 
     fn init_jump_table(&self) -> JumpTable {
         let mut jump_table: JumpTable = Default::default();
-        for &(y, x) in self.obstacles.iter() {
-            let states = [
-                ((y - 1, x), Dir::S),
-                ((y + 1, x), Dir::N),
-                ((y, x - 1), Dir::E),
-                ((y, x + 1), Dir::W),
-            ];
+        let obstacles = self.obstacles.iter().filter(|(_, obstacle)| **obstacle);
+        for (obstacle, _) in obstacles {
+            let states = Dir::ALL.map(|dir| (dir.go(obstacle), dir.opposite()));
             for (mut pos, dir) in states.into_iter() {
-                if self.outside_map(&pos) {
+                if !self.obstacles.is_inside(pos) {
                     continue;
                 }
 
@@ -180,10 +163,10 @@ impl Day06 {
                 let dir = dir.turn_right();
                 loop {
                     pos = dir.go(pos);
-                    if self.outside_map(&pos) {
+                    if !self.obstacles.is_inside(pos) {
                         jump_table.insert(start, None);
                         break;
-                    } else if self.obstacles.contains(&pos) {
+                    } else if self.obstacles[pos] {
                         jump_table.insert(start, Some((dir.opposite().go(pos), dir)));
                         break;
                     }
@@ -193,43 +176,4 @@ impl Day06 {
 
         jump_table
     }
-
-    #[inline]
-    fn outside_map(&self, pos: &Coord) -> bool {
-        pos.0 < 0 || pos.1 < 0 || pos.0 >= self.size[0] || pos.1 >= self.size[1]
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use indoc::indoc;
-
-    use super::*;
-
-    const INPUT: &str = indoc! {"
-        ....#.....
-        .........#
-        ..........
-        ..#.......
-        .......#..
-        ..........
-        .#..^.....
-        ........#.
-        #.........
-        ......#...
-    "};
-
-    #[test]
-    fn part_1() {
-        let mut day = Day06::default();
-        day.parse(INPUT);
-        assert_eq!(day.part1(), 41);
-    }
-
-    #[test]
-    fn part_2() {
-        let mut day = Day06::default();
-        day.parse(INPUT);
-        assert_eq!(day.part2(), 6);
-    }
 }
@@ -1,6 +1,6 @@
 //! # Day 07
 
-use aoc_runner::Day;
+use aoc_runner::{Day, ParseError, ParseSlice, SolveError};
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
 #[derive(Default, Clone)]
@@ -48,36 +48,39 @@ impl Day for Day07 {
     type Result1 = u64;
     type Result2 = u64;
 
-    fn parse(&mut self, input: &str) {
+    fn parse(&mut self, input: &str) -> Result<(), ParseError> {
         self.0 = input
             .lines()
             .map(|line| {
-                let (result, parts) = line.split_once(": ").unwrap();
-                Equation {
-                    result: result.parse().unwrap(),
+                let (result, parts) = line.split_once_at(input, ": ")?;
+                Ok(Equation {
+                    result: result.parse_at(input)?,
                     parameters: parts
                         .split_whitespace()
-                        .map(|it| it.parse().unwrap())
-                        .collect(),
-                }
+                        .map(|it| it.parse_at(input))
+                        .collect::<Result<_, _>>()?,
+                })
             })
-            .collect();
+            .collect::<Result<_, ParseError>>()?;
+        Ok(())
     }
 
-    fn part1(&mut self) -> Self::Result1 {
-        self.0
+    fn part1(&mut self) -> Result<Self::Result1, SolveError> {
+        Ok(self
+            .0
             .par_iter()
             .filter(|eq| eq.is_valid(Concatenation::WithoutConcatenation))
             .map(|it| it.result)
-            .sum::<<Self as Day>::Result1>()
+            .sum::<<Self as Day>::Result1>())
     }
 
-    fn part2(&mut self) -> Self::Result2 {
-        self.0
+    fn part2(&mut self) -> Result<Self::Result2, SolveError> {
+        Ok(self
+            .0
             .par_iter()
             .filter(|eq| eq.is_valid(Concatenation::WithConcatenation))
             .map(|it| it.result)
-            .sum::<<Self as Day>::Result1>()
+            .sum::<<Self as Day>::Result1>())
     }
 }
 
@@ -98,8 +101,6 @@ impl Concat for u64 {
 
 #[cfg(test)]
 mod test {
-    use indoc::indoc;
-
     use super::*;
 
     #[test]
@@ -111,30 +112,4 @@ mod test {
         assert_eq!(99_u64.concat(1), 991);
         assert_eq!(100_u64.concat(1), 1001);
     }
-
-    const INPUT: &str = indoc! {"
-        190: 10 19
-        3267: 81 40 27
-        83: 17 5
-        156: 15 6
-        7290: 6 8 6 15
-        161011: 16 10 13
-        192: 17 8 14
-        21037: 9 7 18 13
-        292: 11 6 16 20
-    "};
-
-    #[test]
-    fn part_1() {
-        let mut day = Day07::default();
-        day.parse(INPUT);
-        assert_eq!(day.part1(), 3749);
-    }
-
-    #[test]
-    fn part_2() {
-        let mut day = Day07::default();
-        day.parse(INPUT);
-        assert_eq!(day.part2(), 11387);
-    }
 }
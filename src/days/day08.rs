@@ -1,56 +1,40 @@
 //! # Day 08
 
-use aoc_runner::Day;
+use aoc_runner::{Day, ParseError, SolveError};
 use fxhash::FxHashMap as HashMap;
 use fxhash::FxHashSet as HashSet;
 use itertools::iterate;
 
-use crate::common::transform::Transform;
-
-type Num = i16;
-type Coord = (Num, Num);
+use crate::common::grid::{Coord, Grid};
 
 #[derive(Default, Clone)]
 pub struct Day08 {
-    input: String,
+    map: Grid<char>,
     antennas_by_type: HashMap<char, Vec<Coord>>,
-    size: (usize, usize),
 }
 
 impl Day for Day08 {
     type Result1 = u32;
     type Result2 = u32;
 
-    fn parse(&mut self, input: &str) {
-        self.input = input.to_string();
-
-        self.size = input
-            .lines()
-            .transform(|mut lines| (lines.clone().count(), lines.next().unwrap().chars().count()));
-
-        self.antennas_by_type = input
-            .lines()
-            .enumerate()
-            .flat_map(|(y, line)| {
-                line.chars()
-                    .enumerate()
-                    .filter_map(move |(x, ch)| match ch {
-                        '.' => None,
-                        ch => Some((ch, (y as Num, x as Num))),
-                    })
-            })
-            .fold(Default::default(), |mut map, (ch, pos)| {
-                map.entry(ch).or_default().push(pos);
+    fn parse(&mut self, input: &str) -> Result<(), ParseError> {
+        self.map = input.try_into()?;
+        self.antennas_by_type = self.map.iter().filter(|(_, ch)| **ch != '.').fold(
+            Default::default(),
+            |mut map, (pos, ch)| {
+                map.entry(*ch).or_default().push(pos);
                 map
-            })
+            },
+        );
+        Ok(())
     }
 
-    fn part1(&mut self) -> Self::Result1 {
-        self.solve(|a, b| self.get_antinodes(a, b)) as Self::Result1
+    fn part1(&mut self) -> Result<Self::Result1, SolveError> {
+        Ok(self.solve(|a, b| self.get_antinodes(a, b)) as Self::Result1)
     }
 
-    fn part2(&mut self) -> Self::Result2 {
-        self.solve(|a, b| self.get_antinodes_in_line(a, b)) as Self::Result2
+    fn part2(&mut self) -> Result<Self::Result2, SolveError> {
+        Ok(self.solve(|a, b| self.get_antinodes_in_line(a, b)) as Self::Result2)
     }
 }
 
@@ -61,8 +45,8 @@ impl Day08 {
         F: Fn(&Coord, &Coord) -> I,
     {
         self.antennas_by_type
-            .iter()
-            .flat_map(|(_, poss)| {
+            .values()
+            .flat_map(|poss| {
                 let pairs = poss
                     .iter()
                     .enumerate()
@@ -74,60 +58,16 @@ impl Day08 {
     }
 
     fn get_antinodes(&self, a: &Coord, b: &Coord) -> impl Iterator<Item = Coord> + '_ {
-        let slope = (a.0 - b.0, a.1 - b.1);
-        let antinode1 = (a.0 + slope.0, a.1 + slope.1);
-        let antinode2 = (b.0 - slope.0, b.1 - slope.1);
-        [antinode1, antinode2]
+        let slope = *a - *b;
+        [*a + slope, *b - slope]
             .into_iter()
-            .filter(|pos| self.is_inside_map(pos))
+            .filter(|pos| self.map.is_inside(*pos))
     }
 
     fn get_antinodes_in_line(&self, a: &Coord, b: &Coord) -> impl Iterator<Item = Coord> + '_ {
-        let slope = (a.0 - b.0, a.1 - b.1);
-        let it1 = iterate(*a, move |(y, x)| (y + slope.0, x + slope.1))
-            .take_while(|pos| self.is_inside_map(pos));
-        let it2 = iterate(*b, move |(y, x)| (y - slope.0, x - slope.1))
-            .take_while(|pos| self.is_inside_map(pos));
+        let slope = *a - *b;
+        let it1 = iterate(*a, move |pos| *pos + slope).take_while(|pos| self.map.is_inside(*pos));
+        let it2 = iterate(*b, move |pos| *pos - slope).take_while(|pos| self.map.is_inside(*pos));
         it1.chain(it2)
     }
-
-    fn is_inside_map(&self, a: &Coord) -> bool {
-        a.0 >= 0 && a.0 < self.size.0 as Num && a.1 >= 0 && a.1 < self.size.1 as Num
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use indoc::indoc;
-
-    use super::*;
-
-    const INPUT: &str = indoc! {"
-        ............
-        ........0...
-        .....0......
-        .......0....
-        ....0.......
-        ......A.....
-        ............
-        ............
-        ........A...
-        .........A..
-        ............
-        ............
-    "};
-
-    #[test]
-    fn part_1() {
-        let mut day = Day08::default();
-        day.parse(INPUT);
-        assert_eq!(day.part1(), 14);
-    }
-
-    #[test]
-    fn part_2() {
-        let mut day = Day08::default();
-        day.parse(INPUT);
-        assert_eq!(day.part2(), 34);
-    }
 }
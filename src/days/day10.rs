@@ -1,14 +1,14 @@
 //! # Day 10
 
-use std::collections::VecDeque;
+use aoc_runner::{Day, ParseError, SolveError};
 
-use aoc_runner::Day;
-use fxhash::{FxHashMap, FxHashSet};
+use crate::common::{
+    grid::{Coord, Grid},
+    search::bfs,
+};
 
-type Num = i16;
-type Coord = (Num, Num);
 type Height = u8;
-type Map = FxHashMap<Coord, Height>;
+type Map = Grid<Height>;
 
 #[derive(Default, Clone)]
 pub struct Day10(Map);
@@ -17,24 +17,19 @@ impl Day for Day10 {
     type Result1 = u32;
     type Result2 = u32;
 
-    fn parse(&mut self, input: &str) {
-        self.0 = input
-            .lines()
-            .enumerate()
-            .flat_map(|(y, line)| {
-                line.chars()
-                    .enumerate()
-                    .map(move |(x, ch)| ((y as Num, x as Num), ch.to_digit(10).unwrap() as Height))
-            })
-            .collect();
+    fn parse(&mut self, input: &str) -> Result<(), ParseError> {
+        self.0 = Grid::parse(input, "a height digit", |ch| {
+            ch.to_digit(10).map(|height| height as Height)
+        })?;
+        Ok(())
     }
 
-    fn part1(&mut self) -> Self::Result1 {
-        self.0.iter().map(|(pos, h)| self.walk(*pos, *h).0).sum()
+    fn part1(&mut self) -> Result<Self::Result1, SolveError> {
+        Ok(self.0.iter().map(|(pos, h)| self.walk(pos, *h).0).sum())
     }
 
-    fn part2(&mut self) -> Self::Result2 {
-        self.0.iter().map(|(pos, h)| self.walk(*pos, *h).1).sum()
+    fn part2(&mut self) -> Result<Self::Result2, SolveError> {
+        Ok(self.0.iter().map(|(pos, h)| self.walk(pos, *h).1).sum())
     }
 }
 
@@ -44,57 +39,27 @@ impl Day10 {
             return (0, 0);
         }
 
-        let mut trail_ends: FxHashSet<Coord> = Default::default();
-        let mut num_trails = 0;
-        let mut queue = VecDeque::from([(pos, height)]);
-        while let Some((pos, height)) = queue.pop_front() {
-            if height == 9 {
-                trail_ends.insert(pos);
-                num_trails += 1;
-                continue;
-            }
-
-            for (dy, dx) in [(0, 1), (0, -1), (1, 0), (-1, 0)] {
-                let new_pos = (pos.0 + dy, pos.1 + dx);
-                let new_heigt = self.0.get(&new_pos).cloned().unwrap_or(255);
-                if new_heigt == height + 1 {
-                    queue.push_back((new_pos, new_heigt));
-                }
-            }
-        }
+        // every trail climbs one step at a time, so all trails to an end are shortest paths
+        let map = &self.0;
+        let search = bfs(
+            [pos],
+            |pos| {
+                let height = map[*pos];
+                map.neighbours(*pos)
+                    .filter(move |next| map[*next] == height + 1)
+            },
+            |_| false,
+        );
+        let trail_ends = search
+            .costs()
+            .keys()
+            .filter(|pos| map[**pos] == 9)
+            .collect::<Vec<_>>();
+        let num_trails = trail_ends
+            .iter()
+            .map(|end| search.count_paths(end) as <Self as Day>::Result2)
+            .sum();
 
         (trail_ends.len() as <Self as Day>::Result1, num_trails)
     }
 }
-
-#[cfg(test)]
-mod test {
-    use indoc::indoc;
-
-    use super::*;
-
-    const INPUT: &str = indoc! {"
-        89010123
-        78121874
-        87430965
-        96549874
-        45678903
-        32019012
-        01329801
-        10456732
-    "};
-
-    #[test]
-    fn part_1() {
-        let mut day = Day10::default();
-        day.parse(INPUT);
-        assert_eq!(day.part1(), 36);
-    }
-
-    #[test]
-    fn part_2() {
-        let mut day = Day10::default();
-        day.parse(INPUT);
-        assert_eq!(day.part2(), 81);
-    }
-}
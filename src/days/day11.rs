@@ -1,6 +1,6 @@
 //! # Day 11G
 
-use aoc_runner::Day;
+use aoc_runner::{Day, ParseError, ParseSlice, SolveError};
 use cached::proc_macro::cached;
 use fxhash::FxHashMap;
 use num::Integer;
@@ -67,40 +67,27 @@ impl Day for Day11 {
     type Result1 = usize;
     type Result2 = usize;
 
-    fn parse(&mut self, input: &str) {
+    fn parse(&mut self, input: &str) -> Result<(), ParseError> {
         for s in input.split_whitespace() {
-            let num = s.parse().unwrap();
+            let num = s.parse_at(input)?;
             self.0.insert(num, 1);
         }
+        Ok(())
     }
 
-    fn part1(&mut self) -> Self::Result1 {
+    fn part1(&mut self) -> Result<Self::Result1, SolveError> {
         let mut stones = self.0.clone();
         for _ in 0..25 {
             stones.blink();
         }
-        stones.num_stones()
+        Ok(stones.num_stones())
     }
 
-    fn part2(&mut self) -> Self::Result2 {
+    fn part2(&mut self) -> Result<Self::Result2, SolveError> {
         let mut stones = self.0.clone();
         for _ in 0..75 {
             stones.blink();
         }
-        stones.num_stones()
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    const INPUT: &str = "125 17";
-
-    #[test]
-    fn part_1() {
-        let mut day = Day11::default();
-        day.parse(INPUT);
-        assert_eq!(day.part1(), 55312);
+        Ok(stones.num_stones())
     }
 }
@@ -2,49 +2,43 @@
 
 use std::collections::VecDeque;
 
-use aoc_runner::Day;
-use fxhash::{FxHashMap, FxHashSet};
+use aoc_runner::{Day, ParseError, SolveError};
+use fxhash::FxHashSet;
 
-use crate::common::dir::Dir;
-
-type C = i16;
-type Coord = (C, C);
+use crate::common::{
+    dir::Dir,
+    grid::{Coord, Grid},
+};
 
 #[derive(Default, Clone)]
-struct Garden(FxHashMap<Coord, char>);
+struct Garden(Grid<char>);
 
 impl Garden {
     fn areas(&self) -> Vec<Area> {
         let mut areas: Vec<Area> = Default::default();
         let mut coords_to_skip: FxHashSet<Coord> = Default::default();
-        let coords = self.0.keys();
 
-        for coord in coords {
-            if coords_to_skip.contains(coord) {
+        for (coord, plant) in self.0.iter() {
+            if coords_to_skip.contains(&coord) {
                 continue;
             }
 
             let mut area = Area {
-                plant: *self.0.get(coord).unwrap(),
+                plant: *plant,
                 fields: Default::default(),
             };
 
             let mut visited: FxHashSet<Coord> = Default::default();
-            let mut agenda = VecDeque::from([*coord]);
-            while let Some(coord @ (y, x)) = agenda.pop_front() {
+            let mut agenda = VecDeque::from([coord]);
+            while let Some(coord) = agenda.pop_front() {
                 if !visited.insert(coord) {
                     continue;
                 }
 
-                if let Some(plant) = self.0.get(&coord) {
-                    if *plant == area.plant {
-                        area.insert(coord);
-                        coords_to_skip.insert(coord);
-
-                        for (dy, dx) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
-                            agenda.push_back((y + dy, x + dx));
-                        }
-                    }
+                if self.0[coord] == area.plant {
+                    area.insert(coord);
+                    coords_to_skip.insert(coord);
+                    agenda.extend(self.0.neighbours(coord));
                 }
             }
 
@@ -73,7 +67,7 @@ impl Area {
     fn perimeter(&self) -> u32 {
         self.fields
             .iter()
-            .flat_map(|&(y, x)| [(y + 1, x), (y - 1, x), (y, x + 1), (y, x - 1)])
+            .flat_map(|coord| coord.neighbours())
             .filter(|coord| !self.fields.contains(coord))
             .count() as u32
     }
@@ -125,99 +119,16 @@ impl Day for Day12 {
     type Result1 = u32;
     type Result2 = u32;
 
-    fn parse(&mut self, input: &str) {
-        let garden = Garden(
-            input
-                .lines()
-                .enumerate()
-                .flat_map(|(y, line)| {
-                    line.chars()
-                        .enumerate()
-                        .map(move |(x, ch)| ((y as C, x as C), ch))
-                })
-                .collect(),
-        );
-        self.0 = garden;
+    fn parse(&mut self, input: &str) -> Result<(), ParseError> {
+        self.0 = Garden(input.try_into()?);
+        Ok(())
     }
 
-    fn part1(&mut self) -> Self::Result1 {
-        self.0.areas().iter().map(Area::price).sum()
+    fn part1(&mut self) -> Result<Self::Result1, SolveError> {
+        Ok(self.0.areas().iter().map(Area::price).sum())
     }
 
-    fn part2(&mut self) -> Self::Result2 {
-        self.0.areas().iter().map(Area::discount_price).sum()
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use indoc::indoc;
-
-    use super::*;
-
-    const INPUT: &str = indoc! {"
-        AAAA
-        BBCD
-        BBCC
-        EEEC
-    "};
-
-    const INPUT2: &str = indoc! {"
-        OOOOO
-        OXOXO
-        OOOOO
-        OXOXO
-        OOOOO
-    "};
-
-    const INPUT3: &str = indoc! {"
-        RRRRIICCFF
-        RRRRIICCCF
-        VVRRRCCFFF
-        VVRCCCJFFF
-        VVVVCJJCFE
-        VVIVCCJJEE
-        VVIIICJJEE
-        MIIIIIJJEE
-        MIIISIJEEE
-        MMMISSJEEE
-    "};
-
-    #[test]
-    fn part_1() {
-        {
-            let mut day = Day12::default();
-            day.parse(INPUT);
-            assert_eq!(day.part1(), 140);
-        }
-        {
-            let mut day = Day12::default();
-            day.parse(INPUT2);
-            assert_eq!(day.part1(), 772);
-        }
-        {
-            let mut day = Day12::default();
-            day.parse(INPUT3);
-            assert_eq!(day.part1(), 1930);
-        }
-    }
-
-    #[test]
-    fn part_2() {
-        {
-            let mut day = Day12::default();
-            day.parse(INPUT);
-            assert_eq!(day.part2(), 80);
-        }
-        {
-            let mut day = Day12::default();
-            day.parse(INPUT2);
-            assert_eq!(day.part2(), 436);
-        }
-        {
-            let mut day = Day12::default();
-            day.parse(INPUT3);
-            assert_eq!(day.part2(), 1206);
-        }
+    fn part2(&mut self) -> Result<Self::Result2, SolveError> {
+        Ok(self.0.areas().iter().map(Area::discount_price).sum())
     }
 }
@@ -1,53 +1,100 @@
 //! # Day 14
 
-use std::fmt::{Display, Write};
+use std::{
+    fmt::{Display, Write},
+    num::NonZeroUsize,
+};
 
-use aoc_runner::Day;
+use aoc_runner::{Day, ParamError, Params, ParseError, ParseSlice, SolveError};
 
-use crate::common::chinese_remainder::chinese_remainder;
+use crate::common::{chinese_remainder::chinese_remainder, vector::Vector};
 
 type Num = i32;
+type Coord = Vector<Num, 2>;
 
 #[derive(Debug, Default, Clone)]
 struct Robot {
-    pos: (Num, Num),
-    velocity: (Num, Num),
+    pos: Coord,
+    velocity: Coord,
 }
 
 impl Robot {
-    fn pos_at(&self, seconds: u32) -> (Num, Num) {
-        (
-            self.pos.0 + seconds as Num * self.velocity.0,
-            self.pos.1 + seconds as Num * self.velocity.1,
-        )
+    /// Position after `seconds`, wrapped around the edges of a space of `size`
+    fn pos_at(&self, seconds: u32, size: Coord) -> Coord {
+        (self.pos + self.velocity * seconds as Num).rem_euclid(size)
     }
 }
 
-impl From<&str> for Robot {
-    fn from(value: &str) -> Self {
-        let (p, v) = value.split_once(" ").unwrap();
-        let (px, py) = p[2..].split_once(",").unwrap();
-        let (vx, vy) = v[2..].split_once(",").unwrap();
-        let pos = (py.parse().unwrap(), px.parse().unwrap());
-        let velocity = (vy.parse().unwrap(), vx.parse().unwrap());
-        Self { pos, velocity }
+impl Robot {
+    /// Parses a robot from `line`, which must be a slice of `input`
+    fn parse(input: &str, line: &str) -> Result<Self, ParseError> {
+        let (p, v) = line.split_once_at(input, " ")?;
+        let (px, py) = p.strip_prefix_at(input, "p=")?.split_once_at(input, ",")?;
+        let (vx, vy) = v.strip_prefix_at(input, "v=")?.split_once_at(input, ",")?;
+        let pos = Vector([py.parse_at(input)?, px.parse_at(input)?]);
+        let velocity = Vector([vy.parse_at(input)?, vx.parse_at(input)?]);
+        Ok(Self { pos, velocity })
     }
 }
 
 #[derive(Debug, Default, Clone)]
-struct Robots<const H: usize, const W: usize>(Vec<Robot>);
+struct Robots(Vec<Robot>);
 
-impl<const H: usize, const W: usize> Robots<H, W> {
+impl Robots {
     fn iter(&self) -> impl Iterator<Item = &Robot> {
         self.0.iter()
     }
 }
 
-impl<const H: usize, const W: usize> Display for Robots<H, W> {
+impl TryFrom<&str> for Robots {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let robots = value
+            .lines()
+            .map(|line| Robot::parse(value, line))
+            .collect::<Result<_, _>>()?;
+        Ok(Self(robots))
+    }
+}
+
+/// Size of the space the robots move in, which is smaller for the examples
+#[derive(Debug, Clone, Copy)]
+struct Size {
+    height: usize,
+    width: usize,
+}
+
+impl Size {
+    fn as_coord(&self) -> Coord {
+        Vector([self.height as Num, self.width as Num])
+    }
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Self {
+            height: 103,
+            width: 101,
+        }
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct Day14 {
+    size: Size,
+    robots: Robots,
+}
+
+impl Display for Day14 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for y in 0..=H {
-            for x in 0..=W {
-                if self.iter().any(|r| r.pos == (y as Num, x as Num)) {
+        for y in 0..=self.size.height {
+            for x in 0..=self.size.width {
+                if self
+                    .robots
+                    .iter()
+                    .any(|r| r.pos == Vector([y as Num, x as Num]))
+                {
                     f.write_char('@')?;
                 } else {
                     f.write_char('.')?;
@@ -59,74 +106,74 @@ impl<const H: usize, const W: usize> Display for Robots<H, W> {
     }
 }
 
-impl<const H: usize, const W: usize> From<&str> for Robots<H, W> {
-    fn from(value: &str) -> Self {
-        let robots = value.lines().map(Robot::from).collect();
-        Self(robots)
-    }
-}
-
-#[derive(Default, Clone)]
-pub struct Day14<const H: usize = 103, const W: usize = 101>(Robots<H, W>);
-
-impl<const H: usize, const W: usize> Day for Day14<H, W> {
+impl Day for Day14 {
     type Result1 = usize;
     type Result2 = u32;
 
-    fn parse(&mut self, input: &str) {
-        self.0 = input.into();
+    fn configure(&mut self, params: &Params) -> Result<(), ParamError> {
+        params.expect(&["height", "width"])?;
+        // an empty room has no quadrants and robots can't wrap around it
+        if let Some(height) = params.get::<NonZeroUsize>("height")? {
+            self.size.height = height.get();
+        }
+        if let Some(width) = params.get::<NonZeroUsize>("width")? {
+            self.size.width = width.get();
+        }
+        Ok(())
+    }
+
+    fn parse(&mut self, input: &str) -> Result<(), ParseError> {
+        self.robots = input.try_into()?;
+        Ok(())
     }
 
-    fn part1(&mut self) -> Self::Result1 {
+    fn part1(&mut self) -> Result<Self::Result1, SolveError> {
+        let Size { height, width } = self.size;
         let num_robots_per_quadrant = self
-            .0
+            .robots
             .iter()
-            .map(|r| r.pos_at(100))
-            .map(|(y, x)| {
-                (
-                    y.rem_euclid(H as Num) as usize,
-                    x.rem_euclid(W as Num) as usize,
-                )
-            })
+            .map(|r| r.pos_at(100, self.size.as_coord()))
+            .map(|pos| (pos.y() as usize, pos.x() as usize))
             .fold([0; 4], |mut acc, pos| {
                 #[allow(clippy::comparison_chain)]
-                if pos.0 < ((H - 1) / 2) {
-                    if pos.1 < ((W - 1) / 2) {
+                if pos.0 < ((height - 1) / 2) {
+                    if pos.1 < ((width - 1) / 2) {
                         acc[0] += 1;
-                    } else if pos.1 > ((W - 1) / 2) {
+                    } else if pos.1 > ((width - 1) / 2) {
                         acc[1] += 1;
                     }
-                } else if pos.0 > ((H - 1) / 2) {
-                    if pos.1 < ((W - 1) / 2) {
+                } else if pos.0 > ((height - 1) / 2) {
+                    if pos.1 < ((width - 1) / 2) {
                         acc[2] += 1;
-                    } else if pos.1 > ((W - 1) / 2) {
+                    } else if pos.1 > ((width - 1) / 2) {
                         acc[3] += 1;
                     }
                 }
                 acc
             });
 
-        num_robots_per_quadrant
+        Ok(num_robots_per_quadrant
             .into_iter()
             .reduce(|a, b| a * b)
-            .unwrap()
+            .unwrap())
     }
 
     /// # Part 2
     ///
-    /// As all robot's x-values repeat with a cycle length of W and all robot's y-values repeat with
-    /// a cylce length of H, find the cycle offset, that indicates a anomaly for the x and the y
+    /// As all robot's x-values repeat with a cycle length of the width and all robot's y-values repeat
+    /// with a cylce length of the height, find the cycle offset, that indicates a anomaly for the x and the y
     /// dimension indepentently and calclutate the moment those pattern appear together via
     /// chinese reminder theorem.
     ///
-    /// Hint: this only works if H and W are prime.
-    fn part2(&mut self) -> Self::Result2 {
-        let mut y_counts = [0; H];
-        let mut x_counts = [0; W];
+    /// Hint: this only works if height and width are prime.
+    fn part2(&mut self) -> Result<Self::Result2, SolveError> {
+        let Size { height, width } = self.size;
+        let mut y_counts = vec![0; height];
+        let mut x_counts = vec![0; width];
         let mut y_iter = (0, 0);
         let mut x_iter = (0, 0);
 
-        for i in 1..W.max(H) {
+        for i in 1..width.max(height) {
             for n in y_counts.iter_mut() {
                 *n = 0;
             }
@@ -134,18 +181,12 @@ impl<const H: usize, const W: usize> Day for Day14<H, W> {
                 *n = 0;
             }
 
-            self.0
+            self.robots
                 .iter()
-                .map(|r| r.pos_at(i as u32))
-                .map(|(y, x)| {
-                    (
-                        y.rem_euclid(H as Num) as usize,
-                        x.rem_euclid(W as Num) as usize,
-                    )
-                })
-                .for_each(|(y, x)| {
-                    y_counts[y] += 1;
-                    x_counts[x] += 1;
+                .map(|r| r.pos_at(i as u32, self.size.as_coord()))
+                .for_each(|pos| {
+                    y_counts[pos.y() as usize] += 1;
+                    x_counts[pos.x() as usize] += 1;
                 });
 
             let y_max = y_counts.iter().max().unwrap();
@@ -158,36 +199,11 @@ impl<const H: usize, const W: usize> Day for Day14<H, W> {
             }
         }
 
-        chinese_remainder(&[y_iter.0 as i64, x_iter.0 as i64], &[H as i64, W as i64]).unwrap()
-            as <Self as Day>::Result2
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use indoc::indoc;
-
-    use super::*;
-
-    const INPUT: &str = indoc! {"
-        p=0,4 v=3,-3
-        p=6,3 v=-1,-3
-        p=10,3 v=-1,2
-        p=2,0 v=2,-1
-        p=0,0 v=1,3
-        p=3,0 v=-2,-2
-        p=7,6 v=-1,-3
-        p=3,0 v=-1,-2
-        p=9,3 v=2,3
-        p=7,3 v=-1,2
-        p=2,4 v=2,-3
-        p=9,5 v=-3,-3
-    "};
-
-    #[test]
-    fn part_1() {
-        let mut day = Day14::<7, 11>::default();
-        day.parse(INPUT);
-        assert_eq!(day.part1(), 12);
+        chinese_remainder(
+            &[y_iter.0 as i64, x_iter.0 as i64],
+            &[height as i64, width as i64],
+        )
+        .map(|it| it as <Self as Day>::Result2)
+        .ok_or(SolveError::NoSolution)
     }
 }
@@ -2,14 +2,14 @@
 
 use std::fmt::{Display, Write};
 
-use aoc_runner::Day;
+use aoc_runner::{Day, ParseError, ParseSlice, SolveError};
 use fxhash::FxHashMap;
-use fxhash::FxHashSet;
 
-use crate::common::dir::Dir;
-
-type Num = i16;
-type Coord = (Num, Num);
+use crate::common::{
+    dir::Dir,
+    grid::{Coord, Grid},
+    vector::Vector,
+};
 
 #[derive(Clone)]
 enum Box {
@@ -20,43 +20,41 @@ enum Box {
 
 #[derive(Default, Clone)]
 struct Warehouse {
-    walls: FxHashSet<Coord>,
+    /// Whether a cell is a wall
+    walls: Grid<bool>,
     boxes: FxHashMap<Coord, Box>,
     robot: Coord,
 }
 
 impl Warehouse {
-    fn from(value: &str, wide: bool) -> Self {
-        let mut walls: FxHashSet<Coord> = Default::default();
+    /// Creates the warehouse of `map`, whose cells are twice as wide if `wide` is set
+    fn new(map: &Grid<char>, wide: bool) -> Self {
+        let scale = if wide { 2 } else { 1 };
+        let mut walls = Grid::new(map.height(), map.width() * scale, false);
         let mut boxes: FxHashMap<Coord, Box> = Default::default();
         let mut robot: Coord = Default::default();
-        value.lines().enumerate().for_each(|(y, line)| {
-            line.chars().enumerate().for_each(|(x, ch)| match ch {
+        map.iter().for_each(|(Vector([y, x]), ch)| {
+            let pos = Vector([y, x * scale as i16]);
+            match ch {
                 '#' => {
-                    if !wide {
-                        walls.insert((y as Num, x as Num));
-                    } else {
-                        walls.insert((y as Num, x as Num * 2));
-                        walls.insert((y as Num, x as Num * 2 + 1));
+                    walls[pos] = true;
+                    if wide {
+                        walls[Dir::E.go(pos)] = true;
                     }
                 }
                 'O' => {
                     if !wide {
-                        boxes.insert((y as Num, x as Num), Box::Single);
+                        boxes.insert(pos, Box::Single);
                     } else {
-                        boxes.insert((y as Num, x as Num * 2), Box::Left);
-                        boxes.insert((y as Num, x as Num * 2 + 1), Box::Right);
+                        boxes.insert(pos, Box::Left);
+                        boxes.insert(Dir::E.go(pos), Box::Right);
                     }
                 }
                 '@' => {
-                    if !wide {
-                        robot = (y as Num, x as Num);
-                    } else {
-                        robot = (y as Num, x as Num * 2);
-                    }
+                    robot = pos;
                 }
                 _ => {}
-            })
+            }
         });
         Self {
             walls,
@@ -71,7 +69,7 @@ impl Warehouse {
             if self.move_box(next_pos, dir, true) {
                 self.robot = next_pos;
             }
-        } else if !self.walls.contains(&next_pos) {
+        } else if !self.walls[next_pos] {
             self.robot = next_pos;
         }
     }
@@ -94,11 +92,7 @@ impl Warehouse {
             return true;
         }
 
-        if self.walls.contains(&next_pos)
-            || other_next_pos
-                .map(|p| self.walls.contains(&p))
-                .unwrap_or(false)
-        {
+        if self.walls[next_pos] || other_next_pos.map(|p| self.walls[p]).unwrap_or(false) {
             false
         } else if self.boxes.contains_key(&next_pos)
             || other_next_pos
@@ -144,7 +138,7 @@ impl Warehouse {
         }
     }
 
-    fn box_gps((y, x): Coord) -> u32 {
+    fn box_gps(Vector([y, x]): Coord) -> u32 {
         y as u32 * 100 + x as u32
     }
 
@@ -161,19 +155,18 @@ impl Warehouse {
 
 impl Display for Warehouse {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let max_y = self.walls.iter().map(|(y, _)| y).max().unwrap();
-        let max_x = self.walls.iter().map(|(_, x)| x).max().unwrap();
-        for y in 0..=*max_y {
-            for x in 0..=*max_x {
-                if self.walls.contains(&(y, x)) {
+        for y in 0..self.walls.height() as i16 {
+            for x in 0..self.walls.width() as i16 {
+                let pos = Vector([y, x]);
+                if self.walls[pos] {
                     f.write_char('#')?;
-                } else if self.boxes.contains_key(&(y, x)) {
-                    match self.boxes.get(&(y, x)).unwrap() {
+                } else if self.boxes.contains_key(&pos) {
+                    match self.boxes.get(&pos).unwrap() {
                         Box::Single => f.write_char('O')?,
                         Box::Left => f.write_char('[')?,
                         Box::Right => f.write_char(']')?,
                     }
-                } else if self.robot == (y, x) {
+                } else if self.robot == pos {
                     f.write_char('@')?;
                 } else {
                     f.write_char('.')?;
@@ -187,7 +180,7 @@ impl Display for Warehouse {
 
 #[derive(Default, Clone)]
 pub struct Day15 {
-    warehouse_str: String,
+    map: Grid<char>,
     moves: Vec<Dir>,
 }
 
@@ -195,94 +188,38 @@ impl Day for Day15 {
     type Result1 = u32;
     type Result2 = u32;
 
-    fn parse(&mut self, input: &str) {
-        let (warehouse, movements) = input.split_once("\n\n").unwrap();
-        self.warehouse_str = warehouse.into();
+    fn parse(&mut self, input: &str) -> Result<(), ParseError> {
+        let (warehouse, movements) = input.split_once_at(input, "\n\n")?;
+        self.map = Grid::parse(warehouse, "a warehouse cell", |ch| match ch {
+            '#' | 'O' | '@' | '.' => Some(ch),
+            _ => None,
+        })?;
         self.moves = movements
-            .chars()
-            .filter(|c| *c != '\n')
-            .map(|c| Dir::try_from(c).unwrap())
-            .collect();
+            .char_indices()
+            .filter(|(_, c)| *c != '\n')
+            .map(|(offset, c)| {
+                Dir::try_from(c).map_err(|_| {
+                    let found = &movements[offset..offset + c.len_utf8()];
+                    ParseError::at(input, found, "a movement")
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(())
     }
 
-    fn part1(&mut self) -> Self::Result1 {
-        let mut warehouse = Warehouse::from(&self.warehouse_str, false);
+    fn part1(&mut self) -> Result<Self::Result1, SolveError> {
+        let mut warehouse = Warehouse::new(&self.map, false);
         for m in self.moves.iter() {
             warehouse.move_robot(*m);
         }
-        warehouse.box_gps_sum()
+        Ok(warehouse.box_gps_sum())
     }
 
-    fn part2(&mut self) -> Self::Result2 {
-        let mut warehouse = Warehouse::from(&self.warehouse_str, true);
+    fn part2(&mut self) -> Result<Self::Result2, SolveError> {
+        let mut warehouse = Warehouse::new(&self.map, true);
         for m in self.moves.iter() {
             warehouse.move_robot(*m);
         }
-        warehouse.box_gps_sum()
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use indoc::indoc;
-
-    use super::*;
-
-    const INPUT1: &str = indoc! {"
-        ########
-        #..O.O.#
-        ##@.O..#
-        #...O..#
-        #.#.O..#
-        #...O..#
-        #......#
-        ########
-
-        <^^>>>vv<v>>v<<
-    "};
-
-    const INPUT2: &str = indoc! {"
-        ##########
-        #..O..O.O#
-        #......O.#
-        #.OO..O.O#
-        #..O@..O.#
-        #O#..O...#
-        #O..O..O.#
-        #.OO.O.OO#
-        #....O...#
-        ##########
-
-        <vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
-        vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
-        ><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
-        <<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
-        ^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
-        ^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
-        >^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
-        <><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
-        ^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
-        v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^
-    "};
-
-    #[test]
-    fn part_1() {
-        {
-            let mut day = Day15::default();
-            day.parse(INPUT1);
-            assert_eq!(day.part1(), 2028);
-        }
-        {
-            let mut day = Day15::default();
-            day.parse(INPUT2);
-            assert_eq!(day.part1(), 10092);
-        }
-    }
-
-    #[test]
-    fn part_2() {
-        let mut day = Day15::default();
-        day.parse(INPUT2);
-        assert_eq!(day.part2(), 9021);
+        Ok(warehouse.box_gps_sum())
     }
 }
@@ -2,8 +2,7 @@
 
 use std::collections::VecDeque;
 
-use aoc_runner::Day;
-use itertools::Itertools;
+use aoc_runner::{Answer, Day, ParseError, ParseSlice, SolveError};
 
 type Num = u128;
 
@@ -13,61 +12,69 @@ struct Machine {
 }
 
 impl Machine {
-    fn execute(&mut self, code: &[Num]) -> Vec<Num> {
+    fn execute(&mut self, code: &[Num]) -> Result<Vec<Num>, SolveError> {
         let mut ip = 0;
         let mut out: Vec<Num> = Default::default();
 
         while let (Some(op), Some(operand)) = (code.get(ip), code.get(ip + 1)) {
-            self.execute_op(*op, *operand, &mut ip, &mut out);
+            self.execute_op(*op, *operand, &mut ip, &mut out)?;
         }
 
-        out
+        Ok(out)
     }
 
-    fn execute_op(&mut self, op: Num, operand: Num, ip: &mut usize, out: &mut Vec<Num>) {
+    fn execute_op(
+        &mut self,
+        op: Num,
+        operand: Num,
+        ip: &mut usize,
+        out: &mut Vec<Num>,
+    ) -> Result<(), SolveError> {
         match op {
             0 => {
-                self.registers[0] >>= self.get_combo_value(operand);
+                self.registers[0] >>= self.get_combo_value(operand)?;
             }
             1 => {
                 self.registers[1] ^= operand;
             }
             2 => {
-                self.registers[1] = self.get_combo_value(operand) & 7;
+                self.registers[1] = self.get_combo_value(operand)? & 7;
             }
             3 => {
                 if self.registers[0] != 0 {
                     *ip = operand as usize;
-                    return;
+                    return Ok(());
                 }
             }
             4 => {
                 self.registers[1] ^= self.registers[2];
             }
             5 => {
-                out.push(self.get_combo_value(operand) & 7);
+                out.push(self.get_combo_value(operand)? & 7);
             }
             6 => {
-                self.registers[1] = self.registers[0] >> self.get_combo_value(operand);
+                self.registers[1] = self.registers[0] >> self.get_combo_value(operand)?;
             }
             7 => {
-                self.registers[2] = self.registers[0] >> self.get_combo_value(operand);
+                self.registers[2] = self.registers[0] >> self.get_combo_value(operand)?;
             }
             _ => {
-                unreachable!("Bad op {}", op);
+                return Err(SolveError::Invalid(format!("Bad op {}", op)));
             }
         }
 
         *ip += 2;
+        Ok(())
     }
 
-    fn get_combo_value(&self, operand: Num) -> Num {
+    fn get_combo_value(&self, operand: Num) -> Result<Num, SolveError> {
         match operand {
-            0..=3 => operand,
-            4..=6 => self.registers[operand as usize - 4],
-            _ => {
-                panic!("Bad combo operand {}", operand);
-            }
+            0..=3 => Ok(operand),
+            4..=6 => Ok(self.registers[operand as usize - 4]),
+            _ => Err(SolveError::Invalid(format!(
+                "Bad combo operand {}",
+                operand
+            ))),
         }
     }
 }
@@ -75,10 +82,9 @@ impl Machine {
 #[derive(Debug)]
 pub struct Out(Vec<Num>);
 
-impl std::fmt::Display for Out {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let s = self.0.iter().map(|it| it.to_string()).join(",");
-        f.write_str(&s)
+impl From<Out> for Answer {
+    fn from(out: Out) -> Self {
+        out.0.into()
     }
 }
 
@@ -92,24 +98,32 @@ impl Day for Day17 {
     type Result1 = Out;
     type Result2 = u128;
 
-    fn parse(&mut self, input: &str) {
+    fn parse(&mut self, input: &str) -> Result<(), ParseError> {
         let mut lines = input.lines();
+        let mut line = |prefix: &str| {
+            lines
+                .next()
+                .ok_or_else(|| ParseError::end_of_input(input, format!("{:?}", prefix)))?
+                .strip_prefix_at(input, prefix)
+        };
         self.registers = [
-            lines.next().unwrap()[12..].parse().unwrap(),
-            lines.next().unwrap()[12..].parse().unwrap(),
-            lines.next().unwrap()[12..].parse().unwrap(),
+            line("Register A: ")?.parse_at(input)?,
+            line("Register B: ")?.parse_at(input)?,
+            line("Register C: ")?.parse_at(input)?,
         ];
-        self.code = lines.nth(1).unwrap()[9..]
+        line("")?;
+        self.code = line("Program: ")?
             .split(",")
-            .map(|it| it.parse().unwrap())
-            .collect();
+            .map(|it| it.parse_at(input))
+            .collect::<Result<_, _>>()?;
+        Ok(())
     }
 
-    fn part1(&mut self) -> Self::Result1 {
+    fn part1(&mut self) -> Result<Self::Result1, SolveError> {
         let mut machine = Machine {
             registers: self.registers,
         };
-        Out(machine.execute(&self.code))
+        Ok(Out(machine.execute(&self.code)?))
     }
 
     /// Essentially, every loop of the program does the following:
@@ -122,21 +136,22 @@ impl Day for Day17 {
     ///
     /// Therefore building the register value starting with the most significant bits and
     /// appending chunks of three bits allows for finding the smallest result.
-    fn part2(&mut self) -> Self::Result2 {
-        fn run(registers: [Num; 3], code: &[Num], l: usize) -> bool {
+    fn part2(&mut self) -> Result<Self::Result2, SolveError> {
+        fn run(registers: [Num; 3], code: &[Num], l: usize) -> Result<bool, SolveError> {
             let mut machine = Machine { registers };
-            let out = machine.execute(code);
-            out.into_iter()
+            let out = machine.execute(code)?;
+            Ok(out
+                .into_iter()
                 .rev()
                 .zip(code.iter().rev())
                 .take(l)
-                .all(|(a, b)| a == *b)
+                .all(|(a, b)| a == *b))
         }
 
-        fn test(registers: [Num; 3], code: &[Num], l: usize) -> bool {
+        fn test(registers: [Num; 3], code: &[Num], l: usize) -> Result<bool, SolveError> {
             let mut machine = Machine { registers };
-            let out = machine.execute(code);
-            out.len() == l && run(registers, code, l)
+            let out = machine.execute(code)?;
+            Ok(out.len() == l && run(registers, code, l)?)
         }
 
         type State = (Num, usize, usize);
@@ -150,10 +165,10 @@ impl Day for Day17 {
 
         while let Some((n, l, i)) = agenda.pop_front() {
             let registers = [n, self.registers[1], self.registers[2]];
-            if run(registers, &self.code, l) {
+            if run(registers, &self.code, l)? {
                 if l == self.code.len() {
-                    if test(registers, &self.code, l) {
-                        return n;
+                    if test(registers, &self.code, l)? {
+                        return Ok(n);
                     } else {
                         continue;
                     }
@@ -166,42 +181,6 @@ impl Day for Day17 {
             }
         }
 
-        panic!("No solution found")
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-    use indoc::indoc;
-
-    const INPUT1: &str = indoc! {"
-        Register A: 729
-        Register B: 0
-        Register C: 0
-
-        Program: 0,1,5,4,3,0
-    "};
-
-    const INPUT2: &str = indoc! {"
-        Register A: 2024
-        Register B: 0
-        Register C: 0
-
-        Program: 0,3,5,4,3,0
-    "};
-
-    #[test]
-    fn part_1() {
-        let mut day = Day17::default();
-        day.parse(INPUT1);
-        assert_eq!(day.part1().0, vec![4, 6, 3, 5, 6, 3, 5, 2, 1, 0]);
-    }
-
-    #[test]
-    fn part_2() {
-        let mut day = Day17::default();
-        day.parse(INPUT2);
-        assert_eq!(day.part2(), 117440);
+        Err(SolveError::NoSolution)
     }
 }
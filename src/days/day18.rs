@@ -1,145 +1,103 @@
 //! # Day 18
 
-use std::collections::VecDeque;
+use std::num::NonZeroUsize;
 
-use aoc_runner::Day;
-use bit_set::BitSet;
+use aoc_runner::{Day, ParamError, Params, ParseError, ParseSlice, SolveError};
 
-use crate::common::dir::Dir;
+use crate::common::{
+    grid::{Coord, Grid},
+    search::bfs,
+    vector::Vector,
+};
 
 type Num = i16;
-type Coord = (Num, Num);
-type Blocks = BitSet<usize>;
-
-#[derive(Default, Clone)]
-pub struct Day18<const SIZE: usize = 70, const NUM_BLOCKS: usize = 1024>(Vec<Coord>);
-
-impl<const SIZE: usize, const NUM_BLOCKS: usize> Day18<SIZE, NUM_BLOCKS> {
-    fn coord_to_index(coord: Coord) -> usize {
-        coord.0 as usize * (SIZE + 1) + coord.1 as usize
-    }
+/// Whether a cell of the memory space is blocked
+type Blocks = Grid<bool>;
+
+#[derive(Clone)]
+pub struct Day18 {
+    /// Largest coordinate of the memory space, which is smaller for the examples
+    size: usize,
+    /// Number of blocks fallen after the first kilobyte, which is less for the examples
+    num_blocks: usize,
+    blocks: Vec<Coord>,
+}
 
-    fn is_valid(&self, coord: Coord, blocks: &Blocks) -> bool {
-        coord.0 >= 0
-            && coord.0 <= SIZE as Num
-            && coord.1 >= 0
-            && coord.1 <= SIZE as Num
-            && !blocks.contains(Self::coord_to_index(coord))
+impl Default for Day18 {
+    fn default() -> Self {
+        Self {
+            size: 70,
+            num_blocks: 1024,
+            blocks: Vec::new(),
+        }
     }
+}
 
-    fn search(&self, blocks: &Blocks) -> Option<u32> {
-        let start = (0, 0);
-        let end = (SIZE as Num, SIZE as Num);
-
-        let mut agenda: VecDeque<(Coord, u32)> = VecDeque::from([(start, 0)]);
-        let mut visited: BitSet<usize> = Default::default();
-        while let Some((coord, cost)) = agenda.pop_front() {
-            if !visited.insert(Self::coord_to_index(coord)) {
-                continue;
-            }
-            if coord == end {
-                return Some(cost);
-            }
-            for d in Dir::ALL {
-                let coord = d.go(coord);
-                let cost = cost + 1;
-                if self.is_valid(coord, blocks) {
-                    agenda.push_back((coord, cost));
-                }
+impl Day18 {
+    /// The memory space with the first `num_blocks` blocks fallen
+    fn blocks(&self, num_blocks: usize) -> Blocks {
+        let mut blocks = Grid::new(self.size + 1, self.size + 1, false);
+        for coord in self.blocks.iter().take(num_blocks) {
+            if let Some(block) = blocks.get_mut(*coord) {
+                *block = true;
             }
         }
+        blocks
+    }
 
-        None
+    fn search(&self, blocks: &Blocks) -> Option<u32> {
+        let start = Vector([0, 0]);
+        let end = Vector([self.size as Num; 2]);
+
+        bfs(
+            [start],
+            |coord| blocks.neighbours(*coord).filter(|next| !blocks[*next]),
+            |coord| *coord == end,
+        )
+        .goal_cost()
+        .map(|cost| cost as u32)
     }
 }
 
-impl<const SIZE: usize, const NUM_BLOCKS: usize> Day for Day18<SIZE, NUM_BLOCKS> {
+impl Day for Day18 {
     type Result1 = u32;
-    type Result2 = String;
+    type Result2 = (Num, Num);
+
+    fn configure(&mut self, params: &Params) -> Result<(), ParamError> {
+        params.expect(&["size", "blocks"])?;
+        params.apply("size", &mut self.size)?;
+        // part 2 searches for the first blocking byte behind the first `blocks` ones
+        if let Some(num_blocks) = params.get::<NonZeroUsize>("blocks")? {
+            self.num_blocks = num_blocks.get();
+        }
+        Ok(())
+    }
 
-    fn parse(&mut self, input: &str) {
-        self.0 = input
+    fn parse(&mut self, input: &str) -> Result<(), ParseError> {
+        self.blocks = input
             .lines()
             .map(|line| {
-                let (x, y) = line.split_once(",").unwrap();
-                (y.parse().unwrap(), x.parse().unwrap())
+                let (x, y) = line.split_once_at(input, ",")?;
+                Ok(Vector([y.parse_at(input)?, x.parse_at(input)?]))
             })
-            .collect();
-    }
-
-    fn part1(&mut self) -> Self::Result1 {
-        let blocks = self
-            .0
-            .iter()
-            .cloned()
-            .take(NUM_BLOCKS)
-            .map(Self::coord_to_index)
-            .collect();
-        self.search(&blocks).expect("No solution found")
+            .collect::<Result<_, ParseError>>()?;
+        Ok(())
     }
 
-    fn part2(&mut self) -> Self::Result2 {
-        let indices = (NUM_BLOCKS..=self.0.len()).collect::<Vec<_>>();
-        let index = indices.as_slice().partition_point(|&i| {
-            let blocks = self
-                .0
-                .iter()
-                .cloned()
-                .take(i)
-                .map(Self::coord_to_index)
-                .collect();
-            self.search(&blocks).is_some()
-        });
-        let index = index + (NUM_BLOCKS - 1);
-
-        format!("{},{}", self.0[index].1, self.0[index].0)
+    fn part1(&mut self) -> Result<Self::Result1, SolveError> {
+        let blocks = self.blocks(self.num_blocks);
+        self.search(&blocks).ok_or(SolveError::NoSolution)
     }
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-    use indoc::indoc;
 
-    const INPUT: &str = indoc! {"
-        5,4
-        4,2
-        4,5
-        3,0
-        2,1
-        6,3
-        2,4
-        1,5
-        0,6
-        3,3
-        2,6
-        5,1
-        1,2
-        5,5
-        2,5
-        6,5
-        1,4
-        0,4
-        6,4
-        1,1
-        6,1
-        1,0
-        0,5
-        1,6
-        2,0
-    "};
-
-    #[test]
-    fn part_1() {
-        let mut day: Day18<6, 12> = Day18::default();
-        day.parse(INPUT);
-        assert_eq!(day.part1(), 22);
-    }
+    fn part2(&mut self) -> Result<Self::Result2, SolveError> {
+        let indices = (self.num_blocks..=self.blocks.len()).collect::<Vec<_>>();
+        let index = indices
+            .as_slice()
+            .partition_point(|&i| self.search(&self.blocks(i)).is_some());
+        let index = index + (self.num_blocks - 1);
 
-    #[test]
-    fn part_2() {
-        let mut day: Day18<6, 12> = Day18::default();
-        day.parse(INPUT);
-        assert_eq!(day.part2(), "6,1");
+        // the path is never blocked if the partition point lies behind the last block
+        let block = self.blocks.get(index).ok_or(SolveError::NoSolution)?;
+        Ok((block.x(), block.y()))
     }
 }
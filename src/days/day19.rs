@@ -1,6 +1,6 @@
 //! # Day 19
 
-use aoc_runner::Day;
+use aoc_runner::{Day, ParseError, ParseSlice, SolveError};
 use cached::proc_macro::cached;
 use fxhash::FxHashSet;
 
@@ -17,24 +17,27 @@ impl Day for Day19 {
     type Result1 = usize;
     type Result2 = usize;
 
-    fn parse(&mut self, input: &str) {
-        let (towels, designs) = input.split_once("\n\n").unwrap();
+    fn parse(&mut self, input: &str) -> Result<(), ParseError> {
+        let (towels, designs) = input.split_once_at(input, "\n\n")?;
         self.towels = towels.split(", ").map(str::to_owned).collect();
         self.designs = designs.lines().map(str::to_owned).collect();
+        Ok(())
     }
 
-    fn part1(&mut self) -> Self::Result1 {
-        self.designs
+    fn part1(&mut self) -> Result<Self::Result1, SolveError> {
+        Ok(self
+            .designs
             .iter()
             .filter(|d| can_design(&self.towels, d))
-            .count()
+            .count())
     }
 
-    fn part2(&mut self) -> Self::Result2 {
-        self.designs
+    fn part2(&mut self) -> Result<Self::Result2, SolveError> {
+        Ok(self
+            .designs
             .iter()
             .map(|d| num_designs(&self.towels, d))
-            .sum()
+            .sum())
     }
 }
 
@@ -68,36 +71,3 @@ fn num_designs(towels: &FxHashSet<Towel>, design: &str) -> usize {
 
     sum
 }
-
-#[cfg(test)]
-mod test {
-    use super::*;
-    use indoc::indoc;
-
-    const INPUT: &str = indoc! {"
-        r, wr, b, g, bwu, rb, gb, br
-
-        brwrr
-        bggr
-        gbbr
-        rrbgbr
-        ubwu
-        bwurrg
-        brgr
-        bbrgwb
-    "};
-
-    #[test]
-    fn part_1() {
-        let mut day = Day19::default();
-        day.parse(INPUT);
-        assert_eq!(day.part1(), 6);
-    }
-
-    #[test]
-    fn part_2() {
-        let mut day = Day19::default();
-        day.parse(INPUT);
-        assert_eq!(day.part2(), 16);
-    }
-}
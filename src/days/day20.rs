@@ -1,30 +1,31 @@
 //! # Day 20
 
-use aoc_runner::Day;
-use fxhash::FxHashSet;
+use aoc_runner::{Day, ParamError, Params, ParseError, SolveError};
 use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
 
-use crate::common::dir::Dir;
-
-type Num = i16;
-type Coord = (Num, Num);
-
-fn distance(a: Coord, b: Coord) -> usize {
-    (a.0.abs_diff(b.0) + a.1.abs_diff(b.1)) as usize
-}
+use crate::common::{
+    grid::{Coord, Grid},
+    search::bfs,
+};
 
 #[derive(Default, Clone)]
 struct Maze {
-    walls: FxHashSet<Coord>,
+    /// Whether a cell is a wall
+    walls: Grid<bool>,
     start: Coord,
     end: Coord,
 }
 
 impl Maze {
-    fn get_cheats<const THRESHOLD: usize>(&self, cheat_len: usize) -> usize {
-        let course = self.get_course();
+    /// Whether `pos` is a wall, where everything outside of the maze counts as wall
+    fn is_wall(&self, pos: Coord) -> bool {
+        self.walls.get(pos).copied().unwrap_or(true)
+    }
 
-        course
+    fn get_cheats(&self, cheat_len: usize, threshold: usize) -> Result<usize, SolveError> {
+        let course = self.get_course()?;
+
+        Ok(course
             .par_iter()
             .enumerate()
             .map(|(i, p)| {
@@ -32,7 +33,7 @@ impl Maze {
                 let mut j = i + 1;
                 while j < course.len() {
                     let q = course[j];
-                    let d = distance(p.0, q.0);
+                    let d = p.0.manhattan(q.0) as usize;
                     // if distance(p, q) > max_cheat_length, advance q to skip tne next
                     // distance(p, q) - max_cheat_length spots that are definitely also
                     // too far away to be a valid cheat target.
@@ -41,7 +42,7 @@ impl Maze {
                         continue;
                     }
 
-                    if d <= cheat_len && (q.1 - p.1 - d) >= THRESHOLD {
+                    if d <= cheat_len && (q.1 - p.1 - d) >= threshold {
                         num_cheats += 1;
                     }
 
@@ -49,121 +50,80 @@ impl Maze {
                 }
                 num_cheats
             })
-            .sum()
+            .sum())
     }
 
-    fn get_course(&self) -> Vec<(Coord, usize)> {
-        let mut course: Vec<(Coord, usize)> = Default::default();
-        let mut pos = self.start;
-        let mut step = 0;
-
-        let mut dir = *Dir::ALL
-            .iter()
-            .find(|d| !self.walls.contains(&d.go(self.start)))
-            .expect("No start direction");
-
-        loop {
-            course.push((pos, step));
-
-            if pos == self.end {
-                break;
-            }
-
-            dir = *[dir, dir.turn_left(), dir.turn_right()]
-                .iter()
-                .find(|d| !self.walls.contains(&d.go(pos)))
-                .expect("Nowhere to go");
-
-            pos = dir.go(pos);
-
-            step += 1;
-        }
+    /// The positions of the race track from start to end along with their step
+    fn get_course(&self) -> Result<Vec<(Coord, usize)>, SolveError> {
+        let search = bfs(
+            [self.start],
+            |pos| pos.neighbours().filter(|next| !self.is_wall(*next)),
+            |pos| *pos == self.end,
+        );
+        let course = search.path(&self.end).ok_or(SolveError::NoSolution)?;
+        Ok(course
+            .into_iter()
+            .enumerate()
+            .map(|(step, pos)| (pos, step))
+            .collect())
+    }
+}
 
-        course
+impl TryFrom<&str> for Maze {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let map = Grid::parse(value, "a racetrack cell", |ch| match ch {
+            '#' | '.' | 'S' | 'E' => Some(ch),
+            _ => None,
+        })?;
+        let start = map
+            .find(&'S')
+            .ok_or_else(|| ParseError::end_of_input(value, "a start position"))?;
+        let end = map
+            .find(&'E')
+            .ok_or_else(|| ParseError::end_of_input(value, "an end position"))?;
+        let walls = map.map(|ch| *ch == '#');
+
+        Ok(Self { walls, start, end })
     }
 }
 
-impl From<&str> for Maze {
-    fn from(value: &str) -> Self {
-        let mut walls: FxHashSet<Coord> = Default::default();
-        let mut start: Coord = Default::default();
-        let mut end: Coord = Default::default();
-
-        for (y, line) in value.lines().enumerate() {
-            for (x, ch) in line.chars().enumerate() {
-                match ch {
-                    '#' => {
-                        walls.insert((y as Num, x as Num));
-                    }
-                    'S' => {
-                        start = (y as Num, x as Num);
-                    }
-                    'E' => {
-                        end = (y as Num, x as Num);
-                    }
-                    _ => {}
-                }
-            }
+#[derive(Clone)]
+pub struct Day20 {
+    /// Picoseconds a cheat must save at least, which is less for the examples
+    threshold: usize,
+    maze: Maze,
+}
+
+impl Default for Day20 {
+    fn default() -> Self {
+        Self {
+            threshold: 100,
+            maze: Maze::default(),
         }
-        Self { walls, start, end }
     }
 }
 
-#[derive(Default, Clone)]
-pub struct Day20<const THRESHOLD: usize = 100>(Maze);
-
-impl<const THRESHOLD: usize> Day for Day20<THRESHOLD> {
+impl Day for Day20 {
     type Result1 = usize;
     type Result2 = usize;
 
-    fn parse(&mut self, input: &str) {
-        self.0 = input.into();
+    fn configure(&mut self, params: &Params) -> Result<(), ParamError> {
+        params.expect(&["threshold"])?;
+        params.apply("threshold", &mut self.threshold)
     }
 
-    fn part1(&mut self) -> Self::Result1 {
-        self.0.get_cheats::<THRESHOLD>(2)
+    fn parse(&mut self, input: &str) -> Result<(), ParseError> {
+        self.maze = input.try_into()?;
+        Ok(())
     }
 
-    fn part2(&mut self) -> Self::Result2 {
-        self.0.get_cheats::<THRESHOLD>(20)
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use indoc::indoc;
-
-    use super::*;
-
-    const INPUT: &str = indoc! {"
-        ###############
-        #...#...#.....#
-        #.#.#.#.#.###.#
-        #S#...#.#.#...#
-        #######.#.#.###
-        #######.#.#...#
-        #######.#.###.#
-        ###..E#...#...#
-        ###.#######.###
-        #...###...#...#
-        #.#####.#.###.#
-        #.#...#.#.#...#
-        #.#.#.#.#.#.###
-        #...#...#...###
-        ###############
-    "};
-
-    #[test]
-    fn part_1() {
-        let mut day: Day20<2> = Day20::default();
-        day.parse(INPUT);
-        assert_eq!(day.part1(), 44);
+    fn part1(&mut self) -> Result<Self::Result1, SolveError> {
+        self.maze.get_cheats(2, self.threshold)
     }
 
-    #[test]
-    fn part_2() {
-        let mut day: Day20<50> = Day20::default();
-        day.parse(INPUT);
-        assert_eq!(day.part2(), 285);
+    fn part2(&mut self) -> Result<Self::Result2, SolveError> {
+        self.maze.get_cheats(20, self.threshold)
     }
 }
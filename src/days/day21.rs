@@ -6,14 +6,14 @@ use std::{
     iter::repeat_n,
 };
 
-use aoc_runner::Day;
+use aoc_runner::{Day, ParseError, SolveError};
 use fxhash::FxHashMap;
 use itertools::Itertools;
 
-use crate::common::{dir::Dir, transform::Transform};
+use crate::common::{dir::Dir, transform::Transform, vector::Vector};
 
 type Num = i8;
-type Coord = (Num, Num);
+type Coord = Vector<Num, 2>;
 type Path = Vec<Key<Dir>>;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -102,23 +102,23 @@ trait Keypad<T> {
 
     fn all_shortest_paths(from: Coord, to: Coord) -> Vec<Vec<Dir>> {
         use std::cmp::Ordering::*;
-        let y_movement = match to.0.cmp(&from.0) {
+        let y_movement = match to.y().cmp(&from.y()) {
             Less => Dir::N,
             Equal => Dir::N,
             Greater => Dir::S,
         };
-        let x_movement = match to.1.cmp(&from.1) {
+        let x_movement = match to.x().cmp(&from.x()) {
             Less => Dir::W,
             Equal => Dir::W,
             Greater => Dir::E,
         };
 
         let mut p = vec![
-            repeat_n(y_movement, from.0.abs_diff(to.0) as usize)
-                .chain(repeat_n(x_movement, from.1.abs_diff(to.1) as usize))
+            repeat_n(y_movement, from.y().abs_diff(to.y()) as usize)
+                .chain(repeat_n(x_movement, from.x().abs_diff(to.x()) as usize))
                 .collect(),
-            repeat_n(x_movement, from.1.abs_diff(to.1) as usize)
-                .chain(repeat_n(y_movement, from.0.abs_diff(to.0) as usize))
+            repeat_n(x_movement, from.x().abs_diff(to.x()) as usize)
+                .chain(repeat_n(y_movement, from.y().abs_diff(to.y()) as usize))
                 .collect(),
         ];
         p.dedup();
@@ -132,23 +132,23 @@ impl Keypad<char> for NumericKeypad {
     fn key_to_pos(k: Key<char>) -> Coord {
         use self::Key::*;
         match k {
-            Activate => (3, 2),
-            Key('0') => (3, 1),
-            Key('1') => (2, 0),
-            Key('2') => (2, 1),
-            Key('3') => (2, 2),
-            Key('4') => (1, 0),
-            Key('5') => (1, 1),
-            Key('6') => (1, 2),
-            Key('7') => (0, 0),
-            Key('8') => (0, 1),
-            Key('9') => (0, 2),
-            _ => panic!(),
+            Activate => Vector([3, 2]),
+            Key('0') => Vector([3, 1]),
+            Key('1') => Vector([2, 0]),
+            Key('2') => Vector([2, 1]),
+            Key('3') => Vector([2, 2]),
+            Key('4') => Vector([1, 0]),
+            Key('5') => Vector([1, 1]),
+            Key('6') => Vector([1, 2]),
+            Key('7') => Vector([0, 0]),
+            Key('8') => Vector([0, 1]),
+            Key('9') => Vector([0, 2]),
+            Key(_) => unreachable!("parse only accepts digits and A"),
         }
     }
 
     fn is_invalid_pos(pos: Coord) -> bool {
-        pos.0 < 0 || pos.1 < 0 || pos.0 >= 4 || pos.1 >= 3 || pos == (3, 0)
+        pos.y() < 0 || pos.x() < 0 || pos.y() >= 4 || pos.x() >= 3 || pos == Vector([3, 0])
     }
 }
 
@@ -197,7 +197,8 @@ impl Day21 {
                 .skip_while(|&&it| it == '0')
                 .take_while(|&&it| it.is_numeric())
                 .collect::<String>()
-                .transform(|it| it.parse::<usize>().unwrap());
+                // a code without non-zero digits has a numeric part of 0
+                .transform(|it| it.parse::<usize>().unwrap_or(0));
 
             code.insert(0, Key::Activate);
 
@@ -269,51 +270,30 @@ impl Day for Day21 {
     type Result1 = usize;
     type Result2 = usize;
 
-    fn parse(&mut self, input: &str) {
+    fn parse(&mut self, input: &str) -> Result<(), ParseError> {
         self.0 = input
             .lines()
             .map(|line| {
-                line.chars()
-                    .map(|c| if c == 'A' { Key::Activate } else { Key::Key(c) })
+                line.char_indices()
+                    .map(|(offset, c)| match c {
+                        'A' => Ok(Key::Activate),
+                        '0'..='9' => Ok(Key::Key(c)),
+                        _ => {
+                            let found = &line[offset..offset + c.len_utf8()];
+                            Err(ParseError::at(input, found, "a numeric keypad key"))
+                        }
+                    })
                     .collect()
             })
-            .collect();
-    }
-
-    fn part1(&mut self) -> Self::Result1 {
-        self.solve(2)
-    }
-
-    fn part2(&mut self) -> Self::Result2 {
-        self.solve(25)
+            .collect::<Result<_, _>>()?;
+        Ok(())
     }
-}
-
-#[cfg(test)]
-mod test {
-    use indoc::indoc;
-
-    use super::*;
-
-    const INPUT: &str = indoc! {"
-        029A
-        980A
-        179A
-        456A
-        379A
-    "};
 
-    #[test]
-    fn part_1() {
-        let mut day: Day21 = Day21::default();
-        day.parse(INPUT);
-        assert_eq!(day.part1(), 126384);
+    fn part1(&mut self) -> Result<Self::Result1, SolveError> {
+        Ok(self.solve(2))
     }
 
-    #[test]
-    fn part_2() {
-        let mut day: Day21 = Day21::default();
-        day.parse(INPUT);
-        assert_eq!(day.part2(), 154115708116294);
+    fn part2(&mut self) -> Result<Self::Result2, SolveError> {
+        Ok(self.solve(25))
     }
 }
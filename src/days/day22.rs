@@ -1,6 +1,6 @@
 //! # Day 22
 
-use aoc_runner::Day;
+use aoc_runner::{Day, ParseError, ParseSlice, SolveError};
 use itertools::iterate;
 
 type Num = u64;
@@ -21,18 +21,23 @@ impl Day for Day22 {
     type Result1 = Num;
     type Result2 = Num;
 
-    fn parse(&mut self, input: &str) {
-        self.0 = input.lines().map(|line| line.parse().unwrap()).collect();
+    fn parse(&mut self, input: &str) -> Result<(), ParseError> {
+        self.0 = input
+            .lines()
+            .map(|line| line.parse_at(input))
+            .collect::<Result<_, _>>()?;
+        Ok(())
     }
 
-    fn part1(&mut self) -> Self::Result1 {
-        self.0
+    fn part1(&mut self) -> Result<Self::Result1, SolveError> {
+        Ok(self
+            .0
             .iter()
             .map(|&i| iterate(i, |i| next_secret(*i)).nth(2000).unwrap())
-            .sum()
+            .sum())
     }
 
-    fn part2(&mut self) -> Self::Result2 {
+    fn part2(&mut self) -> Result<Self::Result2, SolveError> {
         const MAX_SEQUENCE: usize = ((19 as Num).pow(4) - 1) as usize;
         type SequenceMap = Vec<Num>;
         type Sequence = usize;
@@ -77,41 +82,6 @@ impl Day for Day22 {
             }
         }
 
-        max
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use indoc::indoc;
-
-    use super::*;
-
-    const INPUT1: &str = indoc! {"
-        1
-        10
-        100
-        2024
-    "};
-
-    const INPUT2: &str = indoc! {"
-        1
-        2
-        3
-        2024
-    "};
-
-    #[test]
-    fn part_1() {
-        let mut day: Day22 = Day22::default();
-        day.parse(INPUT1);
-        assert_eq!(day.part1(), 37327623);
-    }
-
-    #[test]
-    fn part_2() {
-        let mut day: Day22 = Day22::default();
-        day.parse(INPUT2);
-        assert_eq!(day.part2(), 23);
+        Ok(max)
     }
 }
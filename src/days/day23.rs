@@ -3,9 +3,7 @@
 //! Possible optimazations (for part 2):
 //! Map node names to indices in a Vec<node>, so one doesn't have to clone Strings all the time
 
-use std::fmt::{Display, Write};
-
-use aoc_runner::Day;
+use aoc_runner::{Answer, Day, ParseError, ParseSlice, SolveError};
 use fxhash::{FxHashMap, FxHashSet};
 type Node = String;
 type Edge = (Node, Node);
@@ -20,14 +18,9 @@ impl Clique {
     }
 }
 
-impl Display for Clique {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(self.0[0].as_str())?;
-        for s in &self.0[1..] {
-            f.write_char(',')?;
-            f.write_str(s.as_str())?;
-        }
-        Ok(())
+impl From<Clique> for Answer {
+    fn from(clique: Clique) -> Self {
+        clique.0.into()
     }
 }
 
@@ -101,14 +94,14 @@ impl Day for Day23 {
     type Result1 = usize;
     type Result2 = Clique;
 
-    fn parse(&mut self, input: &str) {
+    fn parse(&mut self, input: &str) -> Result<(), ParseError> {
         let mut nodes: FxHashSet<Node> = Default::default();
         let mut edges: FxHashSet<Edge> = Default::default();
         let mut neighbours: FxHashMap<Node, FxHashSet<Node>> = Default::default();
 
         for line in input.lines() {
-            let a = line[0..2].to_string();
-            let b = line[3..5].to_string();
+            let (a, b) = line.split_once_at(input, "-")?;
+            let (a, b) = (a.to_string(), b.to_string());
             nodes.insert(a.clone());
             nodes.insert(b.clone());
             edges.insert((a.clone(), b.clone()));
@@ -121,10 +114,12 @@ impl Day for Day23 {
         self.nodes.sort();
         self.edges = edges;
         self.neighbours = neighbours;
+        Ok(())
     }
 
-    fn part1(&mut self) -> Self::Result1 {
-        self.nodes
+    fn part1(&mut self) -> Result<Self::Result1, SolveError> {
+        Ok(self
+            .nodes
             .iter()
             .filter(|n| n.starts_with('t'))
             .map(|n| {
@@ -150,82 +145,18 @@ impl Day for Day23 {
                     0
                 }
             })
-            .sum()
+            .sum())
     }
 
-    fn part2(&mut self) -> Self::Result2 {
+    fn part2(&mut self) -> Result<Self::Result2, SolveError> {
         let result = self
             .bron_kerbosch(
                 &mut Default::default(),
                 &mut self.nodes.iter().cloned().collect(),
                 &mut Default::default(),
             )
-            .expect("No solution found");
-
-        Clique::from(result)
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use indoc::indoc;
-
-    use super::*;
-
-    const INPUT: &str = indoc! {"
-        kh-tc
-        qp-kh
-        de-cg
-        ka-co
-        yn-aq
-        qp-ub
-        cg-tb
-        vc-aq
-        tb-ka
-        wh-tc
-        yn-cg
-        kh-ub
-        ta-co
-        de-co
-        tc-td
-        tb-wq
-        wh-td
-        ta-ka
-        td-qp
-        aq-cg
-        wq-ub
-        ub-vc
-        de-ta
-        wq-aq
-        wq-vc
-        wh-yn
-        ka-de
-        kh-ta
-        co-tc
-        wh-qp
-        tb-vc
-        td-yn
-    "};
-
-    #[test]
-    fn part_1() {
-        let mut day: Day23 = Day23::default();
-        day.parse(INPUT);
-        assert_eq!(day.part1(), 7);
-    }
+            .ok_or(SolveError::NoSolution)?;
 
-    #[test]
-    fn part_2() {
-        let mut day: Day23 = Day23::default();
-        day.parse(INPUT);
-        assert_eq!(
-            day.part2(),
-            Clique::from(vec![
-                String::from("co"),
-                String::from("de"),
-                String::from("ka"),
-                String::from("ta")
-            ])
-        );
+        Ok(Clique::from(result))
     }
 }
